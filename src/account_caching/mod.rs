@@ -1,6 +1,10 @@
+#[cfg(feature = "pubsub")]
+pub mod pubsub_cache;
 pub mod rpc_cache;
+pub mod static_cache;
 
 use solana_account::Account;
+use solana_client::client_error::ClientErrorKind;
 use thiserror::Error;
 
 use async_trait::async_trait;
@@ -21,7 +25,7 @@ use solana_pubkey::Pubkey;
 ///
 /// Typical implementations include:
 /// - `RpcClientCache` (network-backed, concurrent cache)
-/// - Test harnesses using fixed HashMaps
+/// - `StaticAccountsCache` (test harness using a fixed HashMap)
 /// - Simulators like LiteSVM or custom in-process banks
 #[async_trait]
 pub trait AccountsCache: Send + Sync {
@@ -36,6 +40,36 @@ pub trait AccountsCache: Send + Sync {
     /// - Lock acquisition failures
     async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, AccountCacheError>;
 
+    /// Retrieve a single account, bypassing any cached copy.
+    ///
+    /// Use when staleness is unacceptable, e.g. right after submitting a
+    /// transaction that touched the account. Caching implementations should
+    /// read from the source and refresh their entry with the result.
+    ///
+    /// The default implementation calls `get_account`, which is correct for
+    /// caches that never hold stale data.
+    async fn get_account_uncached(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<Account>, AccountCacheError> {
+        self.get_account(pubkey).await
+    }
+
+    /// Retrieve a single account together with the slot it was observed at.
+    ///
+    /// Lets callers reason about freshness, e.g. to populate a venue's
+    /// `last_updated_slot`. Caches that serve a stored copy return the slot
+    /// that copy was fetched at.
+    ///
+    /// The default implementation calls `get_account` and reports slot `0`,
+    /// meaning the slot is unknown.
+    async fn get_account_with_slot(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<(Option<Account>, u64), AccountCacheError> {
+        Ok((self.get_account(pubkey).await?, 0))
+    }
+
     /// Retrieve multiple accounts in a single operation.
     ///
     /// The returned vector must have the same length and ordering as `pubkeys`.
@@ -77,6 +111,21 @@ pub enum AccountCacheError {
     /// Failure acquiring a read lock.
     #[error("Failed to acquire read lock")]
     FailedToAcquireReadLock,
+
+    /// A websocket connection or account subscription could not be opened.
+    #[error("Failed to open account subscription: {0}")]
+    SubscriptionFailed(String),
+
+    /// A batched RPC response did not contain one entry per requested key.
+    #[error("RPC returned {returned} accounts for {requested} requested keys")]
+    ResponseLengthMismatch { requested: usize, returned: usize },
+
+    /// A channel in a multi-threaded fetch pipeline was closed or failed.
+    ///
+    /// Carries the rendered channel error so it stays `Send + Sync`
+    /// regardless of the channel implementation.
+    #[error("Account pipeline channel error: {0}")]
+    ChannelError(String),
 }
 
 /// Ensures `AccountCacheError` satisfies `Send + Sync` at compile time.
@@ -84,3 +133,34 @@ const _: fn() = || {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<AccountCacheError>();
 };
+
+/// `ClientError` is not `Clone`, so a cloned `FailedToFetchAccount` carries
+/// the original client error's rendered message as a custom error.
+impl Clone for AccountCacheError {
+    fn clone(&self) -> Self {
+        match self {
+            AccountCacheError::FailedToFetchAccount(e) => AccountCacheError::FailedToFetchAccount(
+                ClientErrorKind::Custom(e.to_string()).into(),
+            ),
+            AccountCacheError::FailedToAcquireWriteLock => {
+                AccountCacheError::FailedToAcquireWriteLock
+            }
+            AccountCacheError::FailedToAcquireReadLock => {
+                AccountCacheError::FailedToAcquireReadLock
+            }
+            AccountCacheError::SubscriptionFailed(message) => {
+                AccountCacheError::SubscriptionFailed(message.clone())
+            }
+            AccountCacheError::ResponseLengthMismatch {
+                requested,
+                returned,
+            } => AccountCacheError::ResponseLengthMismatch {
+                requested: *requested,
+                returned: *returned,
+            },
+            AccountCacheError::ChannelError(message) => {
+                AccountCacheError::ChannelError(message.clone())
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! A websocket-backed account cache kept live by RPC account subscriptions.
+//!
+//! `RpcClientCache` only learns about account changes when it is asked to
+//! fetch. `PubsubAccountsCache` instead opens an `accountSubscribe` stream for
+//! every subscribed pubkey and writes each notification into a shared
+//! `DashMap`, so reads of subscribed accounts are always served from memory
+//! and reflect the latest state pushed by the node.
+//!
+//! Pubkeys that were never passed to `subscribe()` (or whose subscription
+//! failed or ended) fall back to an internal `RpcClientCache`, so venues can
+//! use this cache without knowing which of their accounts are live. Ending a
+//! subscription drops the key's fallback entry too, so the next read goes to
+//! RPC rather than returning the snapshot taken at subscribe time.
+//!
+//! Available with the `pubsub` feature.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use futures::StreamExt;
+use solana_account::Account;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{nonblocking::pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig};
+use solana_pubkey::Pubkey;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use tokio::task::JoinHandle;
+
+use crate::account_caching::{AccountCacheError, AccountsCache, rpc_cache::RpcClientCache};
+
+/// An `AccountsCache` serving subscribed accounts from a live websocket feed.
+///
+/// Each subscription runs on its own Tokio task; dropping the cache aborts
+/// them all.
+pub struct PubsubAccountsCache {
+    pubsub_client: Arc<PubsubClient>,
+    live: Arc<DashMap<Pubkey, Option<Account>>>,
+    subscriptions: DashMap<Pubkey, JoinHandle<()>>,
+    fallback: Arc<RpcClientCache>,
+}
+
+impl PubsubAccountsCache {
+    /// Connect to the websocket endpoint at `ws_url`, using `rpc_client` for
+    /// initial snapshots and unsubscribed keys.
+    ///
+    /// # Errors
+    /// - `SubscriptionFailed` if the websocket connection cannot be opened
+    pub async fn new(ws_url: &str, rpc_client: RpcClient) -> Result<Self, AccountCacheError> {
+        let pubsub_client = PubsubClient::new(ws_url)
+            .await
+            .map_err(|e| AccountCacheError::SubscriptionFailed(e.to_string()))?;
+
+        Ok(Self {
+            pubsub_client: Arc::new(pubsub_client),
+            live: Arc::new(DashMap::new()),
+            subscriptions: DashMap::new(),
+            fallback: Arc::new(RpcClientCache::new(rpc_client)),
+        })
+    }
+
+    /// Start streaming updates for `pubkeys`.
+    ///
+    /// Keys with an active subscription are skipped; keys whose subscription
+    /// failed or ended are subscribed again. The current value of each new
+    /// key is fetched over RPC so reads are answered before the first
+    /// notification arrives.
+    pub async fn subscribe(&self, pubkeys: &[Pubkey]) -> Result<(), AccountCacheError> {
+        let mut new_keys: Vec<Pubkey> = pubkeys
+            .iter()
+            .filter(|pubkey| !self.is_subscribed(pubkey))
+            .copied()
+            .collect();
+        new_keys.sort_unstable();
+        new_keys.dedup();
+
+        for pubkey in &new_keys {
+            let handle = tokio::spawn(stream_account(
+                self.pubsub_client.clone(),
+                self.live.clone(),
+                self.fallback.clone(),
+                *pubkey,
+            ));
+            if let Some(finished) = self.subscriptions.insert(*pubkey, handle) {
+                finished.abort();
+            }
+        }
+
+        // Seed after subscribing so a notification that already landed is
+        // never overwritten by an older snapshot.
+        let snapshot = self.fallback.get_accounts(&new_keys).await?;
+        for (pubkey, account) in new_keys.into_iter().zip(snapshot) {
+            if self.is_subscribed(&pubkey) {
+                self.live.entry(pubkey).or_insert(account);
+            } else {
+                // The stream ended while the snapshot was in flight.
+                self.fallback.invalidate(&pubkey);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stop streaming updates for `pubkeys`; their reads fall back to RPC.
+    pub fn unsubscribe(&self, pubkeys: &[Pubkey]) {
+        for pubkey in pubkeys {
+            if let Some((_, handle)) = self.subscriptions.remove(pubkey) {
+                handle.abort();
+            }
+            end_subscription(&self.live, &self.fallback, pubkey);
+        }
+    }
+
+    /// Whether `pubkey` has an active subscription.
+    pub fn is_subscribed(&self, pubkey: &Pubkey) -> bool {
+        self.subscriptions
+            .get(pubkey)
+            .is_some_and(|handle| !handle.is_finished())
+    }
+}
+
+impl Drop for PubsubAccountsCache {
+    fn drop(&mut self) {
+        for entry in self.subscriptions.iter() {
+            entry.value().abort();
+        }
+    }
+}
+
+/// Drop `pubkey` from `live` and from the `fallback` cache, so its next
+/// read is fetched over RPC.
+///
+/// The fallback entry goes first: a concurrent read then sees either the
+/// live value or a fresh fetch, never the subscribe-time snapshot.
+fn end_subscription(
+    live: &DashMap<Pubkey, Option<Account>>,
+    fallback: &RpcClientCache,
+    pubkey: &Pubkey,
+) {
+    fallback.invalidate(pubkey);
+    live.remove(pubkey);
+}
+
+/// Mirror `accountSubscribe` notifications for `pubkey` into `live` until the
+/// stream ends, then drop the entry so reads fall back to RPC.
+async fn stream_account(
+    pubsub_client: Arc<PubsubClient>,
+    live: Arc<DashMap<Pubkey, Option<Account>>>,
+    fallback: Arc<RpcClientCache>,
+    pubkey: Pubkey,
+) {
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        ..RpcAccountInfoConfig::default()
+    };
+
+    let (mut updates, _unsubscribe) =
+        match pubsub_client.account_subscribe(&pubkey, Some(config)).await {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                log::warn!("Failed to subscribe to account {pubkey}: {e}");
+                end_subscription(&live, &fallback, &pubkey);
+                return;
+            }
+        };
+
+    while let Some(update) = updates.next().await {
+        match update.value.decode::<Account>() {
+            // Closed accounts are reported with zero lamports; treat them as
+            // missing, as `getMultipleAccounts` does.
+            Some(account) if account.lamports == 0 => {
+                live.insert(pubkey, None);
+            }
+            Some(account) => {
+                live.insert(pubkey, Some(account));
+            }
+            None => log::warn!("Failed to decode account update for {pubkey}"),
+        }
+    }
+
+    log::warn!("Account subscription for {pubkey} ended");
+    end_subscription(&live, &fallback, &pubkey);
+}
+
+#[async_trait]
+impl AccountsCache for PubsubAccountsCache {
+    /// Serve `pubkey` from the live map, or from RPC if it is not subscribed.
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, AccountCacheError> {
+        if let Some(account) = self.live.get(pubkey) {
+            return Ok(account.clone());
+        }
+
+        self.fallback.get_account(pubkey).await
+    }
+
+    /// Live keys are never stale; anything else is re-read over RPC.
+    async fn get_account_uncached(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<Account>, AccountCacheError> {
+        if let Some(account) = self.live.get(pubkey) {
+            return Ok(account.clone());
+        }
+
+        self.fallback.get_account_uncached(pubkey).await
+    }
+
+    /// Serve live keys from memory and fetch the rest in one RPC batch,
+    /// preserving the order of `pubkeys`.
+    async fn get_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Account>>, AccountCacheError> {
+        let mut result: Vec<Option<Option<Account>>> = pubkeys
+            .iter()
+            .map(|pubkey| self.live.get(pubkey).map(|account| account.clone()))
+            .collect();
+
+        let missing: Vec<Pubkey> = pubkeys
+            .iter()
+            .zip(&result)
+            .filter(|(_, account)| account.is_none())
+            .map(|(pubkey, _)| *pubkey)
+            .collect();
+        let mut fetched = self.fallback.get_accounts(&missing).await?.into_iter();
+
+        for slot in result.iter_mut().filter(|account| account.is_none()) {
+            *slot = fetched.next();
+        }
+
+        Ok(result.into_iter().map(Option::flatten).collect())
+    }
+}
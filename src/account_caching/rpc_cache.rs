@@ -12,42 +12,223 @@
 //! - Boundary scanning
 //! - Quoting
 //!
-//! The internal storage uses a `DashMap<Pubkey, Option<Account>>`, making it
-//! both concurrent and lock-free at the application level.
+//! The internal storage uses a `DashMap<Pubkey, CachedAccount>`, making it
+//! both concurrent and lock-free at the application level. Each entry records
+//! the slot it was fetched at, enabling slot-aware invalidation.
 
-use ahash::AHashMap;
+use std::{
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use ahash::{AHashMap, AHashSet};
 use async_trait::async_trait;
 use dashmap::DashMap;
+use futures::future::join_all;
 use solana_account::Account;
+use solana_client::client_error::{ClientError, ClientErrorKind};
 use solana_pubkey::Pubkey;
 use solana_rpc_client::nonblocking::rpc_client::RpcClient;
 
 use crate::account_caching::{AccountCacheError, AccountsCache};
 
+/// A cached account together with the slot at which it was observed.
+///
+/// `account` is `Some(Account)` for found accounts and `None` for known-missing
+/// accounts, which avoids retrying missing accounts on every request.
+#[derive(Debug)]
+pub struct CachedAccount {
+    pub account: Option<Account>,
+
+    /// RPC context slot of the fetch that produced this entry.
+    pub slot: u64,
+
+    /// Access tick of the most recent read or write, used for LRU eviction.
+    /// Atomic so reads can bump it under a shared shard lock.
+    last_used: AtomicU64,
+}
+
+impl Clone for CachedAccount {
+    fn clone(&self) -> Self {
+        Self {
+            account: self.account.clone(),
+            slot: self.slot,
+            last_used: AtomicU64::new(self.last_used.load(Ordering::Relaxed)),
+        }
+    }
+}
+
 /// Internal alias for the in-memory account cache.
-/// Stores `Some(Account)` for found accounts and `None` for known-missing accounts.
+type AccountCache = DashMap<Pubkey, CachedAccount>;
+
+/// Once over capacity, the cache evicts down to `max_entries` less this
+/// fraction of it, so one eviction scan covers the next inserts as well.
+const EVICTION_BATCH_DIVISOR: usize = 10;
+
+/// Maximum number of pubkeys accepted by a single `getMultipleAccounts` RPC call.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Retry policy applied to RPC calls made by `RpcClientCache`.
 ///
-/// Using `Option<Account>` avoids retrying missing accounts on every request.
-type AccountCache = DashMap<Pubkey, Option<Account>>;
+/// Transient failures (I/O errors, timeouts, connection failures, HTTP 429 and
+/// 5xx responses) are retried up to `max_attempts` total attempts, sleeping
+/// `initial_backoff` before the first retry and doubling the delay after each
+/// subsequent one, capped at `max_backoff`. Any other error is returned
+/// immediately.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Returns `true` if an RPC error is likely to succeed when retried.
+fn is_retryable(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::Io(_) => true,
+        ClientErrorKind::Reqwest(error) => {
+            error.is_timeout()
+                || error.is_connect()
+                || error
+                    .status()
+                    .is_some_and(|status| status.as_u16() == 429 || status.is_server_error())
+        }
+        _ => false,
+    }
+}
 
 /// A caching layer around a Solana RPC client.
 ///
 /// The cache performs the following optimizations:
 ///
 /// - **Single-account fetch**: Cache hits avoid RPC calls entirely.
-/// - **Multi-account fetch**: Groups unknown keys into a single `get_multiple_accounts` RPC call.
+/// - **Multi-account fetch**: Groups unknown keys into `get_multiple_accounts` RPC calls of at
+///   most `MAX_MULTIPLE_ACCOUNTS` keys each, issued concurrently.
 /// - **Caching negative lookups**: Accounts that consistently return `None` are also stored.
+/// - **Slot tracking**: Each entry records the context slot it was fetched at, so
+///   callers can drop state older than a given slot via `invalidate_below_slot`.
+/// - **Optional capacity bound**: With `with_capacity`, the least-recently-used
+///   entries are evicted once the cache exceeds `max_entries`.
+/// - **Retries**: Transient RPC failures are retried with exponential backoff
+///   according to a `RetryPolicy`.
 /// - **Thread-safe reads/writes** using `DashMap`.
 pub struct RpcClientCache {
     rpc_client: RpcClient,
     cache: AccountCache,
+    max_entries: Option<usize>,
+    access_counter: AtomicU64,
+    retry_policy: RetryPolicy,
 }
 
 impl RpcClientCache {
-    /// Construct a new RPC cache from an existing `RpcClient`.
+    /// Construct a new, unbounded RPC cache from an existing `RpcClient`.
     pub fn new(rpc_client: RpcClient) -> Self {
         let cache = AccountCache::default();
-        Self { rpc_client, cache }
+        Self {
+            rpc_client,
+            cache,
+            max_entries: None,
+            access_counter: AtomicU64::new(0),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Replace the retry policy used for RPC calls.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Run an RPC call under the configured `RetryPolicy`.
+    ///
+    /// Non-retryable errors, and the last error once attempts are exhausted,
+    /// surface as `AccountCacheError::FailedToFetchAccount`.
+    async fn with_retry<T, F, Fut>(&self, mut call: F) -> Result<T, AccountCacheError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut attempt = 1;
+        let mut backoff = self.retry_policy.initial_backoff;
+
+        loop {
+            match call().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.retry_policy.max_attempts && is_retryable(&e) => {
+                    log::warn!(
+                        "Transient RPC error on attempt {}, retrying: {}",
+                        attempt,
+                        e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2).min(self.retry_policy.max_backoff);
+                    attempt += 1;
+                }
+                Err(e) => return Err(AccountCacheError::FailedToFetchAccount(e)),
+            }
+        }
+    }
+
+    /// Bound the cache to at most `max_entries` accounts.
+    ///
+    /// When an insert pushes the cache over the bound, the least-recently-used
+    /// entries (by read or write) are evicted. Each eviction scans the cache
+    /// once and frees a tenth of the bound beyond the overflow, so the scan is
+    /// amortized over the inserts that follow.
+    pub fn with_capacity(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Next value of the monotonically increasing access tick.
+    fn next_tick(&self) -> u64 {
+        self.access_counter.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Read the entry for `pubkey`, marking it as most recently used.
+    fn touch(&self, pubkey: &Pubkey) -> Option<CachedAccount> {
+        let entry = self.cache.get(pubkey)?;
+        entry.last_used.store(self.next_tick(), Ordering::Relaxed);
+        Some(entry.clone())
+    }
+
+    /// Evict a batch of least-recently-used entries once the capacity bound
+    /// is exceeded.
+    fn evict_over_capacity(&self) {
+        let Some(max_entries) = self.max_entries else {
+            return;
+        };
+        if self.cache.len() <= max_entries {
+            return;
+        }
+
+        let mut by_recency: Vec<(u64, Pubkey)> = self
+            .cache
+            .iter()
+            .map(|entry| (entry.last_used.load(Ordering::Relaxed), *entry.key()))
+            .collect();
+        let target = max_entries - max_entries / EVICTION_BATCH_DIVISOR;
+        let excess = by_recency.len().saturating_sub(target);
+        if excess == 0 {
+            return;
+        }
+
+        by_recency.select_nth_unstable(excess - 1);
+        for (_, pubkey) in &by_recency[..excess] {
+            self.cache.remove(pubkey);
+        }
     }
 
     /// Clear all cached entries.
@@ -57,6 +238,114 @@ impl RpcClientCache {
         self.cache.clear();
     }
 
+    /// Drop every entry fetched before `slot`; entries at or after `slot` are kept.
+    ///
+    /// Useful after a transaction lands at a known slot: anything observed
+    /// earlier may be stale, while newer entries remain valid.
+    pub fn invalidate_below_slot(&self, slot: u64) {
+        self.cache.retain(|_, entry| entry.slot >= slot);
+    }
+
+    /// Drop the entry for `pubkey`, so its next read is fetched over RPC.
+    pub fn invalidate(&self, pubkey: &Pubkey) {
+        self.cache.remove(pubkey);
+    }
+
+    /// Insert (or replace) the entry for `pubkey`, tagged with the slot it was observed at.
+    pub fn insert(&self, pubkey: Pubkey, account: Option<Account>, slot: u64) {
+        let last_used = AtomicU64::new(self.next_tick());
+        self.cache.insert(
+            pubkey,
+            CachedAccount {
+                account,
+                slot,
+                last_used,
+            },
+        );
+        self.evict_over_capacity();
+    }
+
+    /// Return the slot at which `pubkey` was cached, or `None` if it is not cached.
+    pub fn cached_slot(&self, pubkey: &Pubkey) -> Option<u64> {
+        self.cache.get(pubkey).map(|entry| entry.slot)
+    }
+
+    /// Fetch `keys` from RPC in concurrent chunks of at most
+    /// `MAX_MULTIPLE_ACCOUNTS`, caching every result (including `None`)
+    /// with the response's context slot.
+    ///
+    /// Fails with `ResponseLengthMismatch` if a response does not hold one
+    /// entry per requested key; the chunk's results are then not cached.
+    async fn fetch_and_cache(
+        &self,
+        keys: &[Pubkey],
+    ) -> Result<Vec<(Pubkey, Option<Account>)>, AccountCacheError> {
+        let responses = join_all(keys.chunks(MAX_MULTIPLE_ACCOUNTS).map(|chunk| {
+            self.with_retry(move || {
+                self.rpc_client
+                    .get_multiple_accounts_with_commitment(chunk, self.rpc_client.commitment())
+            })
+        }))
+        .await;
+
+        let mut fetched = Vec::with_capacity(keys.len());
+        for (chunk, response) in keys.chunks(MAX_MULTIPLE_ACCOUNTS).zip(responses) {
+            let response = response?;
+            if response.value.len() != chunk.len() {
+                return Err(AccountCacheError::ResponseLengthMismatch {
+                    requested: chunk.len(),
+                    returned: response.value.len(),
+                });
+            }
+
+            let slot = response.context.slot;
+            for (pubkey, account) in chunk.iter().zip(response.value) {
+                self.insert(*pubkey, account.clone(), slot);
+                fetched.push((*pubkey, account));
+            }
+        }
+
+        Ok(fetched)
+    }
+
+    /// Fetch a single account from RPC and cache the result (including
+    /// `None`) with the response's context slot, which is also returned.
+    async fn fetch_account(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<(Option<Account>, u64), AccountCacheError> {
+        let response = self
+            .with_retry(|| {
+                self.rpc_client
+                    .get_account_with_commitment(pubkey, self.rpc_client.commitment())
+            })
+            .await?;
+
+        self.insert(*pubkey, response.value.clone(), response.context.slot);
+
+        Ok((response.value, response.context.slot))
+    }
+
+    /// Prefetch `pubkeys` into the cache using batched RPC calls.
+    ///
+    /// Keys that are already cached are skipped. Missing accounts are cached
+    /// as `None`, so a subsequent `get_accounts` over the same keys is served
+    /// entirely from the cache. Typically called with the output of
+    /// `get_required_pubkeys_for_update()` for many venues at once, before
+    /// their `update_state()` calls.
+    pub async fn warm(&self, pubkeys: &[Pubkey]) -> Result<(), AccountCacheError> {
+        let mut keys: Vec<Pubkey> = pubkeys
+            .iter()
+            .filter(|pubkey| !self.cache.contains_key(pubkey))
+            .copied()
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        self.fetch_and_cache(&keys).await?;
+        Ok(())
+    }
+
     /// Retrieve multiple accounts from the cache without making RPC requests.
     ///
     /// For each pubkey:
@@ -67,8 +356,8 @@ impl RpcClientCache {
     pub fn get_multiple(&self, pubkeys: &[Pubkey]) -> Vec<Option<Account>> {
         let mut result = Vec::with_capacity(pubkeys.len());
         pubkeys.iter().for_each(|key| {
-            if let Some(value) = self.cache.get(key) {
-                result.push(value.clone());
+            if let Some(entry) = self.touch(key) {
+                result.push(entry.account);
             } else {
                 result.push(None);
             }
@@ -83,79 +372,83 @@ impl AccountsCache for RpcClientCache {
     /// Get a single account by pubkey.
     ///
     /// - Cache hit → returned immediately.
-    /// - Cache miss → RPC call made, then result cached.
+    /// - Cache miss → RPC call made, then result (including `None`) cached
+    ///   together with the response's context slot.
     ///
     /// Errors are converted into `AccountCacheError`.
     async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, AccountCacheError> {
-        if let Some(account) = self.cache.get(pubkey) {
-            return Ok(account.to_owned());
+        if let Some(entry) = self.touch(pubkey) {
+            return Ok(entry.account);
         }
 
-        let response: Account = self
-            .rpc_client
-            .get_account(pubkey)
-            .await
-            .map_err(|e| AccountCacheError::FailedToFetchAccount(e))?;
+        let (account, _) = self.fetch_account(pubkey).await?;
+        Ok(account)
+    }
+
+    /// Fetch `pubkey` from RPC even if it is cached, replacing the cached
+    /// entry with the result.
+    async fn get_account_uncached(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<Account>, AccountCacheError> {
+        let (account, _) = self.fetch_account(pubkey).await?;
+        Ok(account)
+    }
 
-        // Cache positive lookup
-        self.cache.insert(*pubkey, Some(response.clone()));
+    /// Like `get_account`, also returning the context slot of the RPC
+    /// response that produced the entry, whether cached or freshly fetched.
+    async fn get_account_with_slot(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<(Option<Account>, u64), AccountCacheError> {
+        if let Some(entry) = self.touch(pubkey) {
+            return Ok((entry.account, entry.slot));
+        }
 
-        Ok(Some(response))
+        self.fetch_account(pubkey).await
     }
 
     /// Fetch multiple accounts, using cached values where possible and batching
-    /// missing keys into a single RPC call.
+    /// missing keys into as few RPC calls as the RPC key limit allows.
     ///
     /// Steps:
     /// 1. Split pubkeys into cache hits and misses.
-    /// 2. Fetch misses using `get_multiple_accounts`.
-    /// 3. Store results (including `None` values) in cache.
+    /// 2. Fetch misses using concurrent `get_multiple_accounts` calls of at
+    ///    most `MAX_MULTIPLE_ACCOUNTS` keys each.
+    /// 3. Store results (including `None` values) in cache, tagged with the
+    ///    response's context slot.
     /// 4. Return accounts in the same order as `pubkeys`.
+    ///
+    /// Duplicate pubkeys are fetched once and each receives the same result.
     async fn get_accounts(
         &self,
         pubkeys: &[Pubkey],
     ) -> Result<Vec<Option<Account>>, AccountCacheError> {
         let mut keys = Vec::new();
+        let mut seen: AHashSet<Pubkey> = AHashSet::default();
         let mut result_map: AHashMap<Pubkey, Option<Account>> = AHashMap::default();
-        let cached_results = self.get_multiple(pubkeys);
 
-        // Identify cache hits and misses
-        cached_results
-            .iter()
-            .zip(pubkeys.iter())
-            .for_each(|(account, pubkey)| {
-                if let Some(res) = account {
-                    // Cached hit
-                    result_map.insert(*pubkey, Some(res.clone()));
-                } else {
-                    // Needs RPC fetch
-                    keys.push(*pubkey);
+        // Identify cache hits (including known-missing accounts) and misses
+        for pubkey in pubkeys {
+            if !seen.insert(*pubkey) {
+                continue;
+            }
+            match self.touch(pubkey) {
+                Some(entry) => {
+                    result_map.insert(*pubkey, entry.account);
                 }
-            });
-
-        // Batch RPC call for missing keys
-        if !keys.is_empty() {
-            let response = self
-                .rpc_client
-                .get_multiple_accounts(&keys)
-                .await
-                .map_err(|e| AccountCacheError::FailedToFetchAccount(e))?;
-
-            // Update map and cache
-            for (pubkey, account) in keys.iter().zip(response.iter()) {
-                result_map.insert(*pubkey, account.clone());
-                self.cache.insert(*pubkey, account.clone());
+                None => keys.push(*pubkey),
             }
         }
 
-        // Reassemble results in original input order
-        let mut result = Vec::new();
+        // Batched RPC calls for missing keys, chunked to the RPC key limit
+        result_map.extend(self.fetch_and_cache(&keys).await?);
 
-        for pubkey in pubkeys {
-            let item = result_map.get(pubkey).unwrap();
-            result.push(item.clone());
-        }
-
-        Ok(result)
+        // Reassemble results in original input order. Every key is present:
+        // `fetch_and_cache` fails rather than return a partial batch.
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| result_map.get(pubkey).cloned().flatten())
+            .collect())
     }
 }
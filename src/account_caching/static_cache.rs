@@ -0,0 +1,58 @@
+//! A fixed, in-memory account cache for deterministic tests.
+//!
+//! `StaticAccountsCache` serves accounts from a `HashMap` populated up front,
+//! so venues can run `update_state()` and `quote()` without an RPC endpoint
+//! or LiteSVM. Keys that were never inserted are reported as missing
+//! (`Ok(None)`); the cache never performs I/O.
+
+use ahash::HashMap;
+use async_trait::async_trait;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+use crate::account_caching::{AccountCacheError, AccountsCache};
+
+/// An `AccountsCache` backed by a fixed snapshot of accounts.
+#[derive(Debug, Clone, Default)]
+pub struct StaticAccountsCache {
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl StaticAccountsCache {
+    /// Construct an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert or replace the account stored under `pubkey`.
+    pub fn insert(&mut self, pubkey: Pubkey, account: Account) -> Option<Account> {
+        self.accounts.insert(pubkey, account)
+    }
+}
+
+impl FromIterator<(Pubkey, Account)> for StaticAccountsCache {
+    fn from_iter<T: IntoIterator<Item = (Pubkey, Account)>>(iter: T) -> Self {
+        Self {
+            accounts: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl AccountsCache for StaticAccountsCache {
+    /// Return the stored account, or `None` if it was never inserted.
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, AccountCacheError> {
+        Ok(self.accounts.get(pubkey).cloned())
+    }
+
+    /// Return stored accounts in the same order as `pubkeys`.
+    async fn get_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Account>>, AccountCacheError> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.accounts.get(pubkey).cloned())
+            .collect())
+    }
+}
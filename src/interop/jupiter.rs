@@ -0,0 +1,105 @@
+//! Jupiter-compatible quote output.
+//!
+//! `to_jupiter_quote` converts a Titan `QuoteRequest`/`QuoteResult` pair into
+//! `JupiterQuote`, whose JSON shape mirrors Jupiter's quote response
+//! (`inAmount`, `outAmount`, `priceImpactPct`, `marketInfos`, ...). This lets
+//! integrators put a Titan venue behind tooling that already consumes Jupiter
+//! quotes.
+//!
+//! As in Jupiter's API, atom amounts are encoded as decimal strings and
+//! percentages as fractional strings (`"0.003"` for 0.3%).
+
+use serde::{Deserialize, Serialize};
+use solana_pubkey::Pubkey;
+
+use crate::trading_venue::{QuoteRequest, QuoteResult, SwapType};
+
+/// A single venue hop, mirroring an entry of Jupiter's `marketInfos`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterMarketInfo {
+    pub label: String,
+    #[serde(with = "crate::trading_venue::serde_pubkey")]
+    pub input_mint: Pubkey,
+    #[serde(with = "crate::trading_venue::serde_pubkey")]
+    pub output_mint: Pubkey,
+    pub not_enough_liquidity: bool,
+    pub in_amount: String,
+    pub out_amount: String,
+    pub price_impact_pct: String,
+    pub lp_fee: JupiterFee,
+}
+
+/// Fee charged by a hop, denominated in `mint`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterFee {
+    pub amount: String,
+    #[serde(with = "crate::trading_venue::serde_pubkey")]
+    pub mint: Pubkey,
+    pub pct: String,
+}
+
+/// A quote in Jupiter's response shape.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterQuote {
+    #[serde(with = "crate::trading_venue::serde_pubkey")]
+    pub input_mint: Pubkey,
+    pub in_amount: String,
+    #[serde(with = "crate::trading_venue::serde_pubkey")]
+    pub output_mint: Pubkey,
+    pub out_amount: String,
+    /// Minimum output after applying `slippage_bps`.
+    pub other_amount_threshold: String,
+    pub swap_mode: String,
+    pub slippage_bps: u64,
+    pub price_impact_pct: String,
+    pub market_infos: Vec<JupiterMarketInfo>,
+}
+
+/// Render a fraction `numerator / denominator` as a decimal string.
+fn pct(numerator: u64, denominator: u64) -> String {
+    if denominator == 0 {
+        return "0".to_string();
+    }
+    (numerator as f64 / denominator as f64).to_string()
+}
+
+/// Convert a Titan quote into Jupiter's quote response shape.
+///
+/// `label` names the venue in `marketInfos` (typically `TradingVenue::label()`).
+/// The slippage threshold is derived from `req.slippage_bps`, exactly as
+/// venues do when building the swap instruction.
+pub fn to_jupiter_quote(req: &QuoteRequest, res: &QuoteResult, label: &str) -> JupiterQuote {
+    let swap_mode = match req.swap_type {
+        SwapType::ExactIn => "ExactIn",
+        SwapType::ExactOut => "ExactOut",
+    };
+    let price_impact_pct = pct(res.price_impact_bps as u64, 10_000);
+
+    JupiterQuote {
+        input_mint: res.input_mint,
+        in_amount: res.amount.to_string(),
+        output_mint: res.output_mint,
+        out_amount: res.expected_output.to_string(),
+        other_amount_threshold: res.minimum_received(req.slippage_bps).to_string(),
+        swap_mode: swap_mode.to_string(),
+        slippage_bps: req.slippage_bps,
+        price_impact_pct: price_impact_pct.clone(),
+        market_infos: vec![JupiterMarketInfo {
+            label: label.to_string(),
+            input_mint: res.input_mint,
+            output_mint: res.output_mint,
+            not_enough_liquidity: res.not_enough_liquidity,
+            in_amount: res.amount.to_string(),
+            out_amount: res.expected_output.to_string(),
+            price_impact_pct,
+            lp_fee: JupiterFee {
+                amount: res.fee_amount.to_string(),
+                mint: res.input_mint,
+                pct: pct(res.fee_amount, res.amount),
+            },
+        }],
+    }
+}
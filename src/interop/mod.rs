@@ -0,0 +1,7 @@
+//! Adapters that present Titan quotes in formats understood by other
+//! Solana routing tooling.
+//!
+//! All adapters are serialization-oriented and therefore only compiled with
+//! the `serde` feature.
+
+pub mod jupiter;
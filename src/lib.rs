@@ -1,3 +1,5 @@
 pub mod account_caching;
+#[cfg(feature = "serde")]
+pub mod interop;
 pub mod trading_venue;
 pub mod  oxedium;
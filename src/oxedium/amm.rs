@@ -1,18 +1,18 @@
 use crate::{
     account_caching::AccountsCache,
     oxedium::{
-        components::compute_swap_math,
-        states::{PriceUpdateV2, SwapIxData, Treasury, Vault},
-        utils::{ANCHOR_DISCRIMINATOR_LEN, OXEDIUM_SEED, TREASURY_SEED, VAULT_SEED},
+        components::{SwapMathResult, compute_swap_math, raw_amount_out},
+        states::{PriceFeedMessage, PriceUpdateV2, SwapIxData, Treasury, Vault, VerificationLevel},
+        utils::{OXEDIUM_SEED, OxediumVenueError, PRICE_EXPONENT, TREASURY_SEED, VAULT_SEED},
     },
     trading_venue::{
-        FromAccount, QuoteRequest, QuoteResult, TradingVenue,
+        FeeBreakdown, FromAccount, QuoteRequest, QuoteResult, SwapType, TradingVenue,
         error::{ErrorInfo, TradingVenueError},
         protocol::PoolProtocol,
         token_info::TokenInfo,
     },
 };
-use ahash::{HashMap, HashMapExt};
+use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
 use async_trait::async_trait;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_account::Account;
@@ -20,12 +20,25 @@ use solana_instruction::{AccountMeta, Instruction};
 use solana_program_pack::Pack;
 use solana_pubkey::Pubkey;
 use solana_sdk::system_program;
-use spl_associated_token_account::get_associated_token_address;
+use solana_sysvar::clock::{self, Clock};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 use spl_token::state::Mint;
+use std::collections::HashMap as StdHashMap;
 
 pub const OXEDIUM_AMM_PROGRAM_ID: Pubkey =
     Pubkey::from_str_const("oxe1SKL52HMLBDT2JQvdxscA1LbVc4EEwwSdNZcnDVH");
 
+/// Default maximum oracle price age accepted by `quote()`.
+pub const DEFAULT_MAX_PRICE_AGE_SECS: u64 = 60;
+
+/// Default widest oracle confidence interval accepted by `quote()`, in bps
+/// of the price.
+pub const DEFAULT_MAX_CONF_BPS: u64 = 200;
+
+/// Compute units budgeted per swap; above the trait default because the
+/// program reads and validates two Pyth price accounts.
+pub const OXEDIUM_COMPUTE_UNIT_HINT: u32 = 300_000;
+
 pub const MINT_ORACLES: &[(Pubkey, Pubkey)] = &[
     (
         Pubkey::from_str_const("So11111111111111111111111111111111111111112"),
@@ -37,6 +50,139 @@ pub const MINT_ORACLES: &[(Pubkey, Pubkey)] = &[
     ),
 ];
 
+/// `pubkey` as its first and last four base58 characters, e.g. `So11..1112`.
+fn short_pubkey(pubkey: &Pubkey) -> String {
+    let full = pubkey.to_string();
+    format!("{}..{}", &full[..4], &full[full.len() - 4..])
+}
+
+/// Convert the output-denominated fees charged by `compute_swap_math` into
+/// input atoms, pro rata to the raw (pre-fee) output.
+///
+/// `amount_in` is the gross input; `result` was computed from what reaches
+/// the vault after the input mint's transfer fee. The protocol share comes
+/// from `treasury.fee_bps`; the LP share is the remainder, so the components
+/// always sum to the converted total. `transfer_fee` covers both legs: the
+/// input fee withheld on the way in and the output fee, converted at the
+/// same rate, withheld on the way out.
+#[inline]
+fn fee_breakdown(
+    amount_in: u64,
+    result: &SwapMathResult,
+    in_token: &TokenInfo,
+    out_token: &TokenInfo,
+) -> FeeBreakdown {
+    let net_in = in_token.amount_after_transfer_fee(amount_in);
+    let transfer_fee_in = amount_in - net_in;
+    if result.raw_amount_out == 0 {
+        return FeeBreakdown {
+            transfer_fee: transfer_fee_in,
+            ..FeeBreakdown::default()
+        };
+    }
+
+    let to_input =
+        |fee_out: u128| (fee_out * net_in as u128 / result.raw_amount_out as u128) as u64;
+
+    let total = to_input(result.lp_fee_amount as u128 + result.protocol_fee_amount as u128);
+    let protocol_fee = to_input(result.protocol_fee_amount as u128);
+    let transfer_fee_out = to_input(out_token.transfer_fee_for(result.net_amount_out) as u128);
+
+    FeeBreakdown {
+        protocol_fee,
+        lp_fee: total - protocol_fee,
+        transfer_fee: transfer_fee_in + transfer_fee_out,
+    }
+}
+
+/// Deviation of the execution price from the oracle (spot) price, in bps.
+///
+/// The oracle price yields `raw_amount_out` for the given input, so the
+/// impact is the share of that output lost to fees and curve adjustments.
+#[inline]
+fn price_impact_bps(result: &SwapMathResult) -> u32 {
+    if result.raw_amount_out == 0 {
+        return 0;
+    }
+
+    let lost = result.raw_amount_out.saturating_sub(result.net_amount_out) as u128;
+    (lost * 10_000 / result.raw_amount_out as u128) as u32
+}
+
+/// Address of the protocol treasury PDA.
+#[inline]
+pub fn treasury_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[OXEDIUM_SEED.as_bytes(), TREASURY_SEED.as_bytes()],
+        &OXEDIUM_AMM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Address of the vault PDA holding `mint` liquidity.
+#[inline]
+pub fn vault_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[VAULT_SEED.as_bytes(), mint.as_ref()],
+        &OXEDIUM_AMM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Rescale a Pyth price onto the fixed `PRICE_EXPONENT` expected by the swap math.
+///
+/// Pyth prices are `price * 10^exponent` pairs; feeds with exponents other
+/// than `PRICE_EXPONENT` are multiplied (or divided) by the difference.
+///
+/// Pyth prices are signed; a non-positive price (possible on some feeds and
+/// on uninitialized data) would wrap to an enormous `u64` if cast directly, so
+/// it is rejected up front. A price that rounds to zero when rescaled is
+/// rejected as well, since the swap math cannot price against it.
+///
+/// # Errors
+/// - `InvalidOraclePrice` if the price is not strictly positive, before or
+///   after rescaling
+/// - `CheckedMathError` if rescaling overflows `u64`
+pub fn normalize_price(
+    message: &PriceFeedMessage,
+    oracle: &Pubkey,
+) -> Result<u64, TradingVenueError> {
+    if message.price <= 0 {
+        return Err(TradingVenueError::InvalidOraclePrice(oracle.into()));
+    }
+
+    let overflow = || {
+        TradingVenueError::CheckedMathError(ErrorInfo::StaticStr(
+            "oracle price exponent rescaling overflowed",
+        ))
+    };
+
+    let mantissa = message.price as u64;
+    let diff = message.exponent as i64 - PRICE_EXPONENT as i64;
+    let factor = u32::try_from(diff.unsigned_abs())
+        .ok()
+        .and_then(|diff| 10u64.checked_pow(diff));
+
+    let price = if diff >= 0 {
+        factor
+            .and_then(|factor| mantissa.checked_mul(factor))
+            .ok_or_else(overflow)?
+    } else {
+        // Scaling down past u64 range simply rounds to zero.
+        factor.map_or(0, |factor| mantissa / factor)
+    };
+
+    if price == 0 {
+        return Err(TradingVenueError::InvalidOraclePrice(oracle.into()));
+    }
+
+    Ok(price)
+}
+
+/// Look up the oracle for `mint` in the default `MINT_ORACLES` registry.
+///
+/// Venues built with a custom registry should use
+/// `OxediumAmmVenue::oracle_for_mint` instead.
 #[inline]
 pub fn oracle_for_mint(mint: &Pubkey) -> Option<Pubkey> {
     MINT_ORACLES
@@ -45,6 +191,60 @@ pub fn oracle_for_mint(mint: &Pubkey) -> Option<Pubkey> {
         .map(|(_, o)| *o)
 }
 
+/// Borsh-encoded form of a fully updated `OxediumAmmVenue`.
+///
+/// Map entries are stored sorted by key so identical states produce identical
+/// bytes. Mints are kept in their packed SPL layout since `Mint` is not borsh.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct OxediumVenueSnapshot {
+    initialized: bool,
+    market: Pubkey,
+    mint_oracles: Vec<(Pubkey, Pubkey)>,
+    vaults: Vec<(Pubkey, Vault)>,
+    mints: Vec<(Pubkey, Vec<u8>)>,
+    oracles: Vec<(Pubkey, PriceUpdateV2)>,
+    treasury: Treasury,
+    token_infos: Vec<TokenInfo>,
+    max_price_age_secs: u64,
+    max_conf_bps: u64,
+    min_liquidity: u64,
+    create_treasury_atas: bool,
+    clock_unix_timestamp: Option<i64>,
+    last_updated_slot: Option<u64>,
+    required_verification_level: VerificationLevel,
+    strict: bool,
+}
+
+/// Collect a map into a `Vec` sorted by key.
+fn sorted_entries<V: Clone>(map: &HashMap<Pubkey, V>) -> Vec<(Pubkey, V)> {
+    let mut entries: Vec<(Pubkey, V)> = map.iter().map(|(k, v)| (*k, v.clone())).collect();
+    entries.sort_by_key(|(k, _)| *k);
+    entries
+}
+
+/// An account `update_state` had to skip, leaving part of the venue unloaded.
+///
+/// Skipped vaults, mints, or oracles make their mint untradable rather than
+/// failing the update, so callers inspect these to tell why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateWarning {
+    /// The account was not returned by the cache.
+    MissingAccount(Pubkey),
+    /// The account was returned but could not be decoded.
+    UndecodableAccount(Pubkey),
+}
+
+impl UpdateWarning {
+    /// The skipped account.
+    pub fn pubkey(&self) -> Pubkey {
+        match self {
+            UpdateWarning::MissingAccount(pubkey) | UpdateWarning::UndecodableAccount(pubkey) => {
+                *pubkey
+            }
+        }
+    }
+}
+
 pub struct OxediumAmmVenue {
     /// Titan lifecycle
     initialized: bool,
@@ -58,165 +258,503 @@ pub struct OxediumAmmVenue {
 
     /// Market id (deterministic)
     pub market: Pubkey,
-}
 
-impl FromAccount for OxediumAmmVenue {
-    fn from_account(pubkey: &Pubkey, _: &Account) -> Result<Self, TradingVenueError> {
-        let initialized = false;
-        let vaults = HashMap::new();
-        let mints = HashMap::new();
-        let oracles = HashMap::new();
+    /// Registry of tradable `(mint, oracle)` pairs; `MINT_ORACLES` by default.
+    pub mint_oracles: Vec<(Pubkey, Pubkey)>,
+
+    /// Maximum accepted age of an oracle price, in seconds.
+    pub max_price_age_secs: u64,
+
+    /// Widest accepted oracle confidence interval, in bps of the price.
+    pub max_conf_bps: u64,
+
+    /// Smallest `current_liquidity`, in atoms, every loaded vault must hold
+    /// for the venue to be tradable. `0` disables the check.
+    pub min_liquidity: u64,
+
+    /// Also create the treasury's ATAs in `generate_swap_instructions`, for
+    /// markets listing mints whose treasury accounts may not exist yet.
+    pub create_treasury_atas: bool,
+
+    /// `unix_timestamp` of the Clock sysvar observed during the last update.
+    pub clock_unix_timestamp: Option<i64>,
+
+    /// `slot` of the Clock sysvar observed during the last update.
+    pub last_updated_slot: Option<u64>,
 
+    /// Minimum Pyth verification level accepted by `quote()`.
+    pub required_verification_level: VerificationLevel,
+
+    /// Fail `update_state` with `MissingState` if any required account is
+    /// missing or undecodable, instead of skipping it.
+    pub strict: bool,
+
+    /// Accounts skipped during the last update.
+    update_warnings: Vec<UpdateWarning>,
+}
+
+impl OxediumAmmVenue {
+    /// Construct an uninitialized venue for `market` trading the given
+    /// `(mint, oracle)` pairs.
+    ///
+    /// `from_account` uses the default `MINT_ORACLES` registry; use this to
+    /// trade newly listed mints without a recompile.
+    pub fn new(market: Pubkey, mint_oracles: Vec<(Pubkey, Pubkey)>) -> Self {
         let treasury = Treasury {
             stoptap: false,
             admin: Pubkey::new_unique(),
             fee_bps: 0,
         };
 
-        Ok(Self {
-            initialized,
-            vaults,
-            mints,
-            oracles,
+        Self {
+            initialized: false,
+            vaults: HashMap::new(),
+            mints: HashMap::new(),
+            oracles: HashMap::new(),
             treasury,
             token_infos: vec![],
-            market: *pubkey,
-        })
+            market,
+            mint_oracles,
+            max_price_age_secs: DEFAULT_MAX_PRICE_AGE_SECS,
+            max_conf_bps: DEFAULT_MAX_CONF_BPS,
+            min_liquidity: 0,
+            create_treasury_atas: false,
+            clock_unix_timestamp: None,
+            last_updated_slot: None,
+            required_verification_level: VerificationLevel::Full,
+            strict: false,
+            update_warnings: Vec::new(),
+        }
     }
-}
 
-#[async_trait]
-impl TradingVenue for OxediumAmmVenue {
-    fn initialized(&self) -> bool {
-        self.initialized
-    }
+    /// Reject mints that are not fully loaded (vault, mint, and oracle) and
+    /// therefore absent from `token_infos`.
+    ///
+    /// A registered mint whose vault loaded but whose oracle did not is
+    /// reported as `MissingOracle`, so a failed fetch is not mistaken for a
+    /// registry problem; anything else is `InvalidMint`.
+    fn ensure_tradable(&self, mint: &Pubkey) -> Result<(), TradingVenueError> {
+        if self.token_infos.iter().any(|t| t.pubkey == *mint) {
+            return Ok(());
+        }
 
-    fn program_id(&self) -> Pubkey {
-        OXEDIUM_AMM_PROGRAM_ID
+        match self.vaults.get(mint) {
+            Some(vault) if !self.oracles.contains_key(&vault.pyth_price_account) => Err(
+                TradingVenueError::MissingOracle(vault.pyth_price_account.into()),
+            ),
+            _ => Err(TradingVenueError::InvalidMint(ErrorInfo::Pubkey(*mint))),
+        }
     }
 
-    fn program_dependencies(&self) -> Vec<Pubkey> {
-        vec![OXEDIUM_AMM_PROGRAM_ID]
+    /// Read decimals for every registry mint straight from its mint account,
+    /// in registry order.
+    ///
+    /// Unlike `decimals()`, this needs no prior `update_state`: only the mint
+    /// accounts are fetched, so it works right after `from_account`.
+    ///
+    /// # Errors
+    /// - `NoAccountFound` if a mint account does not exist
+    /// - `DeserializationFailed` if a mint account cannot be unpacked
+    pub async fn resolve_decimals(
+        &self,
+        cache: &dyn AccountsCache,
+    ) -> Result<Vec<(Pubkey, i32)>, TradingVenueError> {
+        let mints: Vec<Pubkey> = self.mint_oracles.iter().map(|(mint, _)| *mint).collect();
+        let accounts = cache.get_accounts(&mints).await?;
+
+        mints
+            .into_iter()
+            .zip(accounts)
+            .map(|(mint, account)| {
+                let account = account.ok_or(TradingVenueError::NoAccountFound(mint.into()))?;
+                let data = account
+                    .data
+                    .get(..Mint::LEN)
+                    .ok_or(TradingVenueError::DeserializationFailed(mint.into()))?;
+                let mint_data = Mint::unpack(data)
+                    .map_err(|_| TradingVenueError::DeserializationFailed(mint.into()))?;
+
+                Ok((mint, mint_data.decimals as i32))
+            })
+            .collect()
     }
 
-    fn market_id(&self) -> Pubkey {
-        self.market
+    /// Build the swap instruction for `request` with an explicit
+    /// `min_amount_out` floor instead of one derived from a fresh quote and
+    /// `request.slippage_bps`.
+    ///
+    /// # Errors
+    /// - `InactivePoolError` if the treasury's `stoptap` is set
+    /// - `ExactOutNotSupported` for `ExactOut` requests
+    /// - `OracleNotFound` if either mint is not in the registry
+    pub fn generate_swap_instruction_with_min(
+        &self,
+        request: QuoteRequest,
+        user: Pubkey,
+        min_amount_out: u64,
+    ) -> Result<Instruction, TradingVenueError> {
+        if self.treasury.stoptap {
+            return Err(TradingVenueError::InactivePoolError(
+                self.market,
+                PoolProtocol::Oxedium,
+            ));
+        }
+        if request.swap_type == SwapType::ExactOut {
+            return Err(TradingVenueError::ExactOutNotSupported);
+        }
+
+        let accounts = self.get_swap_accounts(request.input_mint, request.output_mint, user)?;
+
+        const DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+        let mut data = Vec::with_capacity(8 + 16);
+        data.extend_from_slice(&DISCRIMINATOR);
+
+        SwapIxData {
+            amount_in: request.amount,
+            min_amount_out,
+        }
+        .serialize(&mut data)
+        .map_err(|e| TradingVenueError::SerializationFailed(ErrorInfo::String(format!("{e:?}"))))?;
+
+        Ok(Instruction {
+            program_id: OXEDIUM_AMM_PROGRAM_ID,
+            accounts,
+            data,
+        })
     }
 
-    fn get_token_info(&self) -> &[TokenInfo] {
-        &self.token_infos
+    /// Look up the oracle for `mint` in this venue's registry.
+    pub fn oracle_for_mint(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.mint_oracles
+            .iter()
+            .find(|(m, _)| m == mint)
+            .map(|(_, o)| *o)
     }
 
-    fn protocol(&self) -> PoolProtocol {
-        PoolProtocol::Oxedium
+    /// Accounts skipped during the last `update_state`, in the order they
+    /// were encountered. Empty if everything loaded.
+    pub fn update_warnings(&self) -> &[UpdateWarning] {
+        &self.update_warnings
     }
 
-    fn tradable_mints(&self) -> Result<Vec<Pubkey>, TradingVenueError> {
-        Ok(MINT_ORACLES.iter().map(|(mint, _)| *mint).collect())
+    /// Quote `request` as if the input and output vaults held
+    /// `liquidity_in` and `liquidity_out` atoms, without mutating the venue.
+    ///
+    /// Runs the same swap math as `quote`, so it supports what-if analysis
+    /// such as stress testing against drained or deepened vaults.
+    ///
+    /// # Errors
+    /// The same as `quote`.
+    pub fn quote_with_liquidity(
+        &self,
+        request: QuoteRequest,
+        liquidity_in: u64,
+        liquidity_out: u64,
+    ) -> Result<QuoteResult, TradingVenueError> {
+        let mut vaults = HashMap::with_capacity(2);
+        for (mint, liquidity) in [
+            (request.input_mint, liquidity_in),
+            (request.output_mint, liquidity_out),
+        ] {
+            if let Some(vault) = self.vaults.get(&mint) {
+                vaults.insert(
+                    mint,
+                    Vault {
+                        current_liquidity: liquidity,
+                        ..*vault
+                    },
+                );
+            }
+        }
+
+        self.quote_with_state(request, &vaults, &self.oracles)
     }
 
-    fn get_required_pubkeys_for_update(&self) -> Result<Vec<Pubkey>, TradingVenueError> {
-        let mut keys = Vec::new();
+    /// Serialize the venue's cached state so it can be restored with
+    /// `from_snapshot` without re-fetching any accounts.
+    ///
+    /// # Errors
+    /// - `SerializationFailed` if the snapshot cannot be encoded
+    pub fn to_snapshot(&self) -> Result<Vec<u8>, TradingVenueError> {
+        let mints = sorted_entries(&self.mints)
+            .into_iter()
+            .map(|(pubkey, mint)| {
+                let mut data = vec![0u8; Mint::LEN];
+                mint.pack_into_slice(&mut data);
+                (pubkey, data)
+            })
+            .collect();
 
-        for (mint, oracle) in MINT_ORACLES.iter() {
-            let vault = Pubkey::find_program_address(
-                &[VAULT_SEED.as_bytes(), mint.as_ref()],
-                &self.program_id(),
-            )
-            .0;
-            keys.push(vault);
-            keys.push(*mint);
-            keys.push(*oracle);
-        }
+        let snapshot = OxediumVenueSnapshot {
+            initialized: self.initialized,
+            market: self.market,
+            mint_oracles: self.mint_oracles.clone(),
+            vaults: sorted_entries(&self.vaults),
+            mints,
+            oracles: sorted_entries(&self.oracles),
+            treasury: self.treasury,
+            token_infos: self.token_infos.clone(),
+            max_price_age_secs: self.max_price_age_secs,
+            max_conf_bps: self.max_conf_bps,
+            min_liquidity: self.min_liquidity,
+            create_treasury_atas: self.create_treasury_atas,
+            clock_unix_timestamp: self.clock_unix_timestamp,
+            last_updated_slot: self.last_updated_slot,
+            required_verification_level: self.required_verification_level,
+            strict: self.strict,
+        };
 
-        Ok(keys)
+        borsh::to_vec(&snapshot).map_err(|e| {
+            TradingVenueError::SerializationFailed(ErrorInfo::String(format!("{e:?}")))
+        })
     }
 
-    async fn update_state(&mut self, cache: &dyn AccountsCache) -> Result<(), TradingVenueError> {
-        let pubkeys = self.get_required_pubkeys_for_update()?;
-        let accounts = cache.get_accounts(&pubkeys).await?;
+    /// Restore a venue from bytes produced by `to_snapshot`.
+    ///
+    /// # Errors
+    /// - `DeserializationFailed` if `data` is not a valid snapshot
+    pub fn from_snapshot(data: &[u8]) -> Result<Self, TradingVenueError> {
+        let snapshot = OxediumVenueSnapshot::try_from_slice(data).map_err(|e| {
+            TradingVenueError::DeserializationFailed(ErrorInfo::String(format!(
+                "venue snapshot: {e}"
+            )))
+        })?;
 
-        let account_map: HashMap<Pubkey, &Account> = pubkeys
-            .iter()
-            .zip(accounts.iter())
-            .filter_map(|(pk, acc)| acc.as_ref().map(|a| (*pk, a)))
-            .collect();
+        let mut mints = HashMap::with_capacity(snapshot.mints.len());
+        for (pubkey, data) in snapshot.mints {
+            let mint = Mint::unpack_unchecked(&data)
+                .map_err(|_| TradingVenueError::DeserializationFailed(pubkey.into()))?;
+            mints.insert(pubkey, mint);
+        }
 
-        for (mint, _) in MINT_ORACLES.iter() {
-            let vault_pda = Pubkey::find_program_address(
-                &[VAULT_SEED.as_bytes(), mint.as_ref()],
-                &self.program_id(),
-            )
-            .0;
+        Ok(Self {
+            initialized: snapshot.initialized,
+            vaults: snapshot.vaults.into_iter().collect(),
+            mints,
+            oracles: snapshot.oracles.into_iter().collect(),
+            treasury: snapshot.treasury,
+            token_infos: snapshot.token_infos,
+            market: snapshot.market,
+            mint_oracles: snapshot.mint_oracles,
+            max_price_age_secs: snapshot.max_price_age_secs,
+            max_conf_bps: snapshot.max_conf_bps,
+            min_liquidity: snapshot.min_liquidity,
+            create_treasury_atas: snapshot.create_treasury_atas,
+            clock_unix_timestamp: snapshot.clock_unix_timestamp,
+            last_updated_slot: snapshot.last_updated_slot,
+            required_verification_level: snapshot.required_verification_level,
+            strict: snapshot.strict,
+            update_warnings: Vec::new(),
+        })
+    }
+
+    /// Load clock, treasury, vault, mint, and oracle state from
+    /// `account_map`, shared by `update_state` and
+    /// `update_state_from_accounts`. Missing or undecodable accounts,
+    /// including those with the wrong Anchor discriminator and vaults whose
+    /// mint or oracle does not match the registry, are skipped and recorded
+    /// in `update_warnings`, unless `strict` is set.
+    ///
+    /// Every account is decoded and validated before anything is written, so
+    /// a rejected update leaves the venue exactly as it was.
+    ///
+    /// # Errors
+    /// - `MissingState` naming the first skipped account, in `strict` mode
+    fn apply_accounts(
+        &mut self,
+        account_map: &HashMap<Pubkey, &Account>,
+    ) -> Result<(), TradingVenueError> {
+        let mut warnings = Vec::new();
+        let mut vaults = Vec::with_capacity(self.mint_oracles.len());
+        let mut oracles = Vec::with_capacity(self.mint_oracles.len());
+        for (mint, oracle) in self.mint_oracles.iter() {
+            let vault_pda = vault_pda(mint);
 
             if let Some(vault_account) = account_map.get(&vault_pda) {
-                if vault_account.data.len() >= std::mem::size_of::<Vault>() {
-                    if let Ok(vault) =
-                        Vault::deserialize(&mut &vault_account.data[ANCHOR_DISCRIMINATOR_LEN..])
-                    {
-                        self.vaults.insert(*mint, vault);
-                    } else {
-                        println!(">>> warning: failed to deserialize vault {:?}", vault_pda);
+                match Vault::try_from_account_data(&vault_account.data) {
+                    Ok(vault) => {
+                        // A layout change in the program would deserialize
+                        // into garbage rather than fail; the embedded mint and
+                        // oracle must match what the vault was derived from.
+                        if vault.token_mint != *mint || vault.pyth_price_account != *oracle {
+                            log::warn!("Vault {} does not match the registry", vault_pda);
+                            warnings.push(UpdateWarning::UndecodableAccount(vault_pda));
+                        } else {
+                            vaults.push((*mint, vault));
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to deserialize vault {}: {}", vault_pda, e);
+                        warnings.push(UpdateWarning::UndecodableAccount(vault_pda));
+                    }
+                }
+            } else {
+                warnings.push(UpdateWarning::MissingAccount(vault_pda));
+            }
+
+            if let Some(oracle_account) = account_map.get(oracle) {
+                match PriceUpdateV2::try_from_account_data(&oracle_account.data) {
+                    Ok(price_data) => oracles.push((*oracle, price_data)),
+                    Err(e) => {
+                        log::warn!("Failed to deserialize oracle {}: {}", oracle, e);
+                        warnings.push(UpdateWarning::UndecodableAccount(*oracle));
                     }
-                } else {
-                    println!(">>> warning: vault account data too small {:?}", vault_pda);
                 }
+            } else {
+                warnings.push(UpdateWarning::MissingAccount(*oracle));
             }
+        }
 
+        let treasury_pda = treasury_pda();
+        let mut treasury = None;
+        if let Some(treasury_account) = account_map.get(&treasury_pda) {
+            match Treasury::try_from_account_data(&treasury_account.data) {
+                Ok(decoded) => treasury = Some(decoded),
+                Err(e) => {
+                    log::warn!("Failed to deserialize treasury {}: {}", treasury_pda, e);
+                    warnings.push(UpdateWarning::UndecodableAccount(treasury_pda));
+                }
+            }
+        } else {
+            warnings.push(UpdateWarning::MissingAccount(treasury_pda));
+        }
+
+        let mut clock = None;
+        if let Some(clock_account) = account_map.get(&clock::ID) {
+            if let Ok(decoded) = clock_account.deserialize_data::<Clock>() {
+                clock = Some(decoded);
+            } else {
+                log::warn!("Failed to deserialize clock sysvar");
+                warnings.push(UpdateWarning::UndecodableAccount(clock::ID));
+            }
+        } else {
+            warnings.push(UpdateWarning::MissingAccount(clock::ID));
+        }
+        let epoch = clock.as_ref().map_or(0, |clock| clock.epoch);
+
+        let mut mints = Vec::with_capacity(self.mint_oracles.len());
+        for (mint, _) in self.mint_oracles.iter() {
             if let Some(mint_account) = account_map.get(mint) {
                 if mint_account.data.len() >= spl_token::state::Mint::LEN {
-                    if let Ok(mint_data) = Mint::unpack(&mint_account.data) {
-                        self.mints.insert(*mint, mint_data);
+                    // Token-2022 mints share the base layout, followed by extensions.
+                    let base = Mint::unpack(&mint_account.data[..Mint::LEN]);
+                    let token_info = TokenInfo::new(mint, mint_account, epoch);
+
+                    if let (Ok(mint_data), Ok(token_info)) = (base, token_info) {
+                        mints.push((*mint, mint_data, token_info));
                     } else {
-                        println!(">>> warning: failed to unpack mint {:?}", mint);
+                        log::warn!("Failed to unpack mint {}", mint);
+                        warnings.push(UpdateWarning::UndecodableAccount(*mint));
                     }
                 } else {
-                    println!(">>> warning: mint account data too small {:?}", mint);
+                    log::warn!("Mint account data too small {}", mint);
+                    warnings.push(UpdateWarning::UndecodableAccount(*mint));
                 }
+            } else {
+                warnings.push(UpdateWarning::MissingAccount(*mint));
             }
         }
 
-        for vault in self.vaults.values() {
-            if let Some(oracle_account) = account_map.get(&vault.pyth_price_account) {
-                if let Ok(price_data) = PriceUpdateV2::try_from_account_data(&oracle_account.data) {
-                    self.oracles.insert(vault.pyth_price_account, price_data);
-                } else {
-                    println!(
-                        ">>> warning: failed to deserialize oracle {:?}",
-                        vault.pyth_price_account
-                    );
-                }
-            }
+        if let Some(warning) = warnings.first().filter(|_| self.strict) {
+            return Err(TradingVenueError::MissingState(warning.pubkey().into()));
+        }
+
+        if let Some(treasury) = treasury {
+            self.treasury = treasury;
+        }
+        // A clock missing from this update must not leave the previous
+        // timestamp behind, or stale prices would pass the freshness check.
+        self.clock_unix_timestamp = clock.as_ref().map(|clock| clock.unix_timestamp);
+        if let Some(clock) = clock {
+            self.last_updated_slot = Some(clock.slot);
+        }
+        // Rebuilt from this update alone, so accounts that went missing or
+        // became undecodable do not keep quoting off their last good state.
+        self.vaults = vaults.into_iter().collect();
+        self.oracles = oracles.into_iter().collect();
+
+        let mut token_infos: HashMap<Pubkey, TokenInfo> =
+            self.token_infos.iter().map(|t| (t.pubkey, *t)).collect();
+        for (mint, mint_data, token_info) in mints {
+            self.mints.insert(mint, mint_data);
+            token_infos.insert(mint, token_info);
         }
 
+        // Only mints with a loaded vault, mint, and oracle are tradable, kept
+        // in registry order so `get_token` indices are stable across updates.
         self.token_infos = self
-            .mints
+            .mint_oracles
             .iter()
-            .map(|(mint_pubkey, mint_data)| TokenInfo {
-                pubkey: *mint_pubkey,
-                decimals: mint_data.decimals as i32,
-                is_token_2022: false,
-                transfer_fee: None,
-                maximum_fee: None,
+            .filter_map(|(mint, _)| {
+                let vault = self.vaults.get(mint)?;
+                if !self.oracles.contains_key(&vault.pyth_price_account) {
+                    return None;
+                }
+                token_infos.get(mint).copied()
             })
             .collect();
 
+        self.update_warnings = warnings;
         self.initialized = true;
         Ok(())
     }
 
-    fn quote(&self, request: QuoteRequest) -> Result<QuoteResult, TradingVenueError> {
+    /// Quote `request` against the vaults in `vaults` and the oracle prices
+    /// in `oracles`.
+    ///
+    /// `quote` passes the state loaded by `update_state`; `quote_async`
+    /// passes freshly read prices and `quote_with_liquidity` hypothetical
+    /// vault balances.
+    fn quote_with_state(
+        &self,
+        request: QuoteRequest,
+        vaults: &HashMap<Pubkey, Vault>,
+        oracles: &HashMap<Pubkey, PriceUpdateV2>,
+    ) -> Result<QuoteResult, TradingVenueError> {
         if !self.initialized {
             return Err(TradingVenueError::NotInitialized(ErrorInfo::StaticStr(
                 "venue not initialized",
             )));
         }
 
-        let vault_in = self.vaults.get(&request.input_mint).ok_or_else(|| {
+        if self.treasury.stoptap {
+            return Err(TradingVenueError::InactivePoolError(
+                self.market,
+                PoolProtocol::Oxedium,
+            ));
+        }
+
+        if request.input_mint == request.output_mint {
+            return Err(TradingVenueError::InvalidMint(ErrorInfo::Pubkey(
+                request.output_mint,
+            )));
+        }
+        self.ensure_tradable(&request.input_mint)?;
+        self.ensure_tradable(&request.output_mint)?;
+
+        if request.swap_type == SwapType::ExactOut {
+            return Err(TradingVenueError::ExactOutNotSupported);
+        }
+
+        // Titan probes with zero input; answer without touching the swap math.
+        if request.amount == 0 {
+            return Ok(QuoteResult {
+                input_mint: request.input_mint,
+                output_mint: request.output_mint,
+                amount: 0,
+                expected_output: 0,
+                fee_amount: 0,
+                fees: FeeBreakdown::default(),
+                price_impact_bps: 0,
+                not_enough_liquidity: false,
+            });
+        }
+
+        let vault_in = vaults.get(&request.input_mint).ok_or_else(|| {
             TradingVenueError::VaultNotFound(ErrorInfo::Pubkey(request.input_mint))
         })?;
 
-        let vault_out = self.vaults.get(&request.output_mint).ok_or_else(|| {
+        let vault_out = vaults.get(&request.output_mint).ok_or_else(|| {
             TradingVenueError::VaultNotFound(ErrorInfo::Pubkey(request.output_mint))
         })?;
 
@@ -229,67 +767,85 @@ impl TradingVenue for OxediumAmmVenue {
             TradingVenueError::InvalidMint(ErrorInfo::Pubkey(request.output_mint))
         })?;
 
-        let price_in_data = self
-            .oracles
-            .get(&vault_in.pyth_price_account)
-            .ok_or(TradingVenueError::OracleNotFound)?;
+        let in_token = self.get_token_by_mint(&request.input_mint)?;
+        let out_token = self.get_token_by_mint(&request.output_mint)?;
 
-        let price_out_data = self
-            .oracles
-            .get(&vault_out.pyth_price_account)
-            .ok_or(TradingVenueError::OracleNotFound)?;
+        let price_in = self.checked_oracle_price(vault_in, oracles)?;
+        let price_out = self.checked_oracle_price(vault_out, oracles)?;
 
-        let full_result = compute_swap_math(
-            request.amount,
-            price_in_data.price_message.price as u64,
-            price_out_data.price_message.price as u64,
-            in_mint.decimals,
-            out_mint.decimals,
-            vault_in,
-            vault_out,
-            &self.treasury,
-        )
-        .map_err(|e| TradingVenueError::MathError(ErrorInfo::String(format!("{e:?}"))))?;
-
-        if full_result.raw_amount_out <= vault_out.current_liquidity {
-            return Ok(QuoteResult {
-                input_mint: request.input_mint,
-                output_mint: request.output_mint,
-                amount: request.amount,
-                expected_output: full_result.net_amount_out,
-                not_enough_liquidity: false,
-            });
+        // The vault only receives the input net of its transfer fee.
+        let swap_math = |amount_in: u64| {
+            compute_swap_math(
+                in_token.amount_after_transfer_fee(amount_in),
+                price_in,
+                price_out,
+                in_mint.decimals,
+                out_mint.decimals,
+                vault_in,
+                vault_out,
+                &self.treasury,
+            )
+        };
+        let fits = |result: &SwapMathResult| result.raw_amount_out <= vault_out.current_liquidity;
+
+        match swap_math(request.amount) {
+            Ok(full_result) if fits(&full_result) => {
+                let fees = fee_breakdown(request.amount, &full_result, in_token, out_token);
+                return Ok(QuoteResult {
+                    input_mint: request.input_mint,
+                    output_mint: request.output_mint,
+                    amount: request.amount,
+                    expected_output: out_token
+                        .amount_after_transfer_fee(full_result.net_amount_out),
+                    fee_amount: fees.total(),
+                    fees,
+                    price_impact_bps: price_impact_bps(&full_result),
+                    not_enough_liquidity: false,
+                });
+            }
+            Ok(_) | Err(OxediumVenueError::NotEnoughLiquidity) => {}
+            Err(e) => {
+                // The swap math also overflows for inputs whose output would
+                // exceed the vault anyway. Those fall through to a partial
+                // fill; anything else is a genuine error.
+                let raw_out = raw_amount_out(
+                    in_token.amount_after_transfer_fee(request.amount),
+                    in_mint.decimals,
+                    out_mint.decimals,
+                    price_in,
+                    price_out,
+                );
+                if matches!(raw_out, Ok(raw_out) if raw_out <= vault_out.current_liquidity) {
+                    return Err(e.into());
+                }
+            }
         }
 
+        // Largest fillable input; amounts the swap math rejects count as too large.
         let mut low: u64 = 0;
         let mut high: u64 = request.amount;
         let mut best_amount_in: u64 = 0;
         let mut best_expected_out: u64 = 0;
+        let mut best_fees = FeeBreakdown::default();
+        let mut best_price_impact_bps: u32 = 0;
 
         while low <= high {
-            let mid = (low + high) / 2;
+            let mid = low + (high - low) / 2;
 
-            let res = compute_swap_math(
-                mid,
-                price_in_data.price_message.price as u64,
-                price_out_data.price_message.price as u64,
-                in_mint.decimals,
-                out_mint.decimals,
-                vault_in,
-                vault_out,
-                &self.treasury,
-            )
-            .map_err(|e| TradingVenueError::MathError(ErrorInfo::String(format!("{e:?}"))))?;
-
-            if res.raw_amount_out <= vault_out.current_liquidity {
-                best_amount_in = mid;
-                best_expected_out = res.net_amount_out;
-                low = mid + 1;
-            } else {
-                if mid == 0 {
-                    break;
+            match swap_math(mid) {
+                Ok(res) if fits(&res) => {
+                    best_amount_in = mid;
+                    best_expected_out = out_token.amount_after_transfer_fee(res.net_amount_out);
+                    best_fees = fee_breakdown(mid, &res, in_token, out_token);
+                    best_price_impact_bps = price_impact_bps(&res);
+                    low = mid + 1;
+                }
+                _ => {
+                    if mid == 0 {
+                        break;
+                    }
+                    high = mid - 1;
                 }
-                high = mid - 1;
             }
         }
 
@@ -298,48 +854,462 @@ impl TradingVenue for OxediumAmmVenue {
             output_mint: request.output_mint,
             amount: best_amount_in,
             expected_output: best_expected_out,
+            fee_amount: best_fees.total(),
+            fees: best_fees,
+            price_impact_bps: best_price_impact_bps,
             not_enough_liquidity: true,
         })
     }
 
-    fn generate_swap_instruction(
+    /// Oracle price for `vault` from `oracles`, validated for freshness and
+    /// verification level and rescaled to `PRICE_EXPONENT`.
+    fn checked_oracle_price(
+        &self,
+        vault: &Vault,
+        oracles: &HashMap<Pubkey, PriceUpdateV2>,
+    ) -> Result<u64, TradingVenueError> {
+        let oracle = &vault.pyth_price_account;
+        let price_update = oracles
+            .get(oracle)
+            .ok_or_else(|| TradingVenueError::MissingOracle(oracle.into()))?;
+
+        self.check_price_freshness(oracle, price_update)?;
+        self.check_price_verification(oracle, price_update)?;
+        self.check_price_confidence(oracle, price_update)?;
+
+        normalize_price(&price_update.price_message, oracle)
+    }
+
+    /// Reject `price_update` if it was published more than `max_price_age_secs`
+    /// before the clock observed in `update_state`.
+    ///
+    /// Without a loaded clock there is no reference time, so every price is
+    /// rejected with `MissingState` naming the Clock sysvar.
+    pub fn check_price_freshness(
+        &self,
+        oracle: &Pubkey,
+        price_update: &PriceUpdateV2,
+    ) -> Result<(), TradingVenueError> {
+        let Some(now) = self.clock_unix_timestamp else {
+            return Err(TradingVenueError::MissingState(clock::ID.into()));
+        };
+
+        let age = now.saturating_sub(price_update.price_message.publish_time);
+        if age > 0 && age as u64 > self.max_price_age_secs {
+            return Err(TradingVenueError::StaleOracle(oracle.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Reject `price_update` unless its verification level meets
+    /// `required_verification_level`.
+    pub fn check_price_verification(
+        &self,
+        oracle: &Pubkey,
+        price_update: &PriceUpdateV2,
+    ) -> Result<(), TradingVenueError> {
+        if !price_update
+            .verification_level
+            .gte(self.required_verification_level)
+        {
+            return Err(TradingVenueError::InsufficientOracleVerification(
+                oracle.into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Reject `price_update` if its confidence interval is wider than
+    /// `max_conf_bps` of the price.
+    ///
+    /// Non-positive prices carry no meaningful confidence ratio and are
+    /// rejected as `InvalidOraclePrice`, as in `normalize_price`.
+    pub fn check_price_confidence(
+        &self,
+        oracle: &Pubkey,
+        price_update: &PriceUpdateV2,
+    ) -> Result<(), TradingVenueError> {
+        let message = &price_update.price_message;
+        if message.price <= 0 {
+            return Err(TradingVenueError::InvalidOraclePrice(oracle.into()));
+        }
+
+        let conf_bps = message.conf as u128 * 10_000 / message.price as u128;
+        if conf_bps > self.max_conf_bps as u128 {
+            return Err(TradingVenueError::OracleConfidenceTooWide(oracle.into()));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromAccount for OxediumAmmVenue {
+    fn from_account(pubkey: &Pubkey, _: &Account) -> Result<Self, TradingVenueError> {
+        Ok(Self::new(*pubkey, MINT_ORACLES.to_vec()))
+    }
+}
+
+#[async_trait]
+impl TradingVenue for OxediumAmmVenue {
+    fn initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// The treasury `stoptap` flag halts all swaps on the protocol.
+    fn is_tradable(&self) -> bool {
+        self.initialized && !self.treasury.stoptap && self.meets_liquidity_threshold()
+    }
+
+    /// Every tradable token's vault holds at least `min_liquidity` atoms.
+    fn meets_liquidity_threshold(&self) -> bool {
+        self.token_infos.iter().all(|token| {
+            self.vaults
+                .get(&token.pubkey)
+                .is_some_and(|vault| vault.current_liquidity >= self.min_liquidity)
+        })
+    }
+
+    fn program_id(&self) -> Pubkey {
+        OXEDIUM_AMM_PROGRAM_ID
+    }
+
+    fn program_dependencies(&self) -> Vec<Pubkey> {
+        vec![OXEDIUM_AMM_PROGRAM_ID]
+    }
+
+    /// The AMM program and the programs it invokes for token transfers and
+    /// account creation, including Token-2022 when a loaded mint uses it.
+    fn required_programs(&self) -> Vec<Pubkey> {
+        let mut programs = vec![
+            OXEDIUM_AMM_PROGRAM_ID,
+            spl_token::ID,
+            spl_associated_token_account::ID,
+            system_program::ID,
+        ];
+        for token in &self.token_infos {
+            let program = token.get_token_program();
+            if !programs.contains(&program) {
+                programs.push(program);
+            }
+        }
+        programs
+    }
+
+    fn compute_unit_hint(&self) -> u32 {
+        OXEDIUM_COMPUTE_UNIT_HINT
+    }
+
+    fn market_id(&self) -> Pubkey {
+        self.market
+    }
+
+    fn get_token_info(&self) -> &[TokenInfo] {
+        &self.token_infos
+    }
+
+    fn protocol(&self) -> PoolProtocol {
+        PoolProtocol::Oxedium
+    }
+
+    /// Each tradable mint's vault `current_liquidity`, in `token_infos` order.
+    fn reserves(&self) -> Result<Vec<(Pubkey, u64)>, TradingVenueError> {
+        self.token_infos
+            .iter()
+            .map(|token| {
+                self.vaults
+                    .get(&token.pubkey)
+                    .map(|vault| (token.pubkey, vault.current_liquidity))
+                    .ok_or_else(|| TradingVenueError::VaultNotFound(token.pubkey.into()))
+            })
+            .collect()
+    }
+
+    fn last_updated_slot(&self) -> Option<u64> {
+        self.last_updated_slot
+    }
+
+    /// Keys in a fixed order: the clock sysvar, the treasury, then the vault,
+    /// mint, and oracle of each registered token in registry order.
+    fn get_required_pubkeys_for_update(&self) -> Result<Vec<Pubkey>, TradingVenueError> {
+        let mut keys = vec![clock::ID, treasury_pda()];
+
+        for (mint, oracle) in self.mint_oracles.iter() {
+            keys.push(vault_pda(mint));
+            keys.push(*mint);
+            keys.push(*oracle);
+        }
+
+        Ok(keys)
+    }
+
+    async fn update_state(&mut self, cache: &dyn AccountsCache) -> Result<(), TradingVenueError> {
+        let pubkeys = self.get_required_pubkeys_for_update()?;
+        let accounts = cache.get_accounts(&pubkeys).await?;
+
+        let account_map: HashMap<Pubkey, &Account> = pubkeys
+            .iter()
+            .zip(accounts.iter())
+            .filter_map(|(pk, acc)| acc.as_ref().map(|a| (*pk, a)))
+            .collect();
+
+        self.apply_accounts(&account_map)
+    }
+
+    fn update_state_from_accounts(
+        &mut self,
+        accounts: &StdHashMap<Pubkey, Account>,
+    ) -> Result<(), TradingVenueError> {
+        let account_map: HashMap<Pubkey, &Account> = self
+            .get_required_pubkeys_for_update()?
+            .into_iter()
+            .filter_map(|pk| accounts.get(&pk).map(|a| (pk, a)))
+            .collect();
+
+        self.apply_accounts(&account_map)
+    }
+
+    /// The treasury's protocol fee, charged on every swap. LP fees depend on
+    /// the vault pair and its imbalance, so they are not included.
+    fn fee_bps(&self) -> Result<u32, TradingVenueError> {
+        if !self.initialized {
+            return Err(TradingVenueError::NotInitialized(ErrorInfo::StaticStr(
+                "venue not initialized",
+            )));
+        }
+
+        u32::try_from(self.treasury.fee_bps).map_err(|_| {
+            TradingVenueError::DataConversionError(ErrorInfo::StaticStr(
+                "treasury fee_bps exceeds u32",
+            ))
+        })
+    }
+
+    fn quote(&self, request: QuoteRequest) -> Result<QuoteResult, TradingVenueError> {
+        self.quote_with_state(request, &self.vaults, &self.oracles)
+    }
+
+    /// Quote against oracle prices read from `cache` rather than the ones
+    /// loaded by the last `update_state`.
+    async fn quote_async(
         &self,
+        cache: &dyn AccountsCache,
         request: QuoteRequest,
+    ) -> Result<QuoteResult, TradingVenueError> {
+        let (Some(oracle_in), Some(oracle_out)) = (
+            self.oracle_for_mint(&request.input_mint),
+            self.oracle_for_mint(&request.output_mint),
+        ) else {
+            // Unknown mints; let `quote` report the appropriate error.
+            return self.quote(request);
+        };
+
+        let keys = [oracle_in, oracle_out];
+        let accounts = cache.get_accounts(&keys).await?;
+
+        let mut oracles = HashMap::with_capacity(keys.len());
+        for (oracle, account) in keys.into_iter().zip(accounts) {
+            let account = account.ok_or(TradingVenueError::NoAccountFound(oracle.into()))?;
+            let price_update = PriceUpdateV2::try_from_account_data(&account.data)
+                .map_err(|_| TradingVenueError::DeserializationFailed(oracle.into()))?;
+            oracles.insert(oracle, price_update);
+        }
+
+        self.quote_with_state(request, &self.vaults, &oracles)
+    }
+
+    /// Oracle mid price: both feeds are USD-denominated on the same
+    /// exponent, so their ratio is already in UI units.
+    fn spot_price(&self, base_ind: u8, quote_ind: u8) -> Result<f64, TradingVenueError> {
+        if !self.initialized {
+            return Err(TradingVenueError::NotInitialized(ErrorInfo::StaticStr(
+                "venue not initialized",
+            )));
+        }
+
+        let base_mint = self.get_token(base_ind as usize)?.pubkey;
+        let quote_mint = self.get_token(quote_ind as usize)?.pubkey;
+
+        let base_vault = self
+            .vaults
+            .get(&base_mint)
+            .ok_or_else(|| TradingVenueError::VaultNotFound(ErrorInfo::Pubkey(base_mint)))?;
+        let quote_vault = self
+            .vaults
+            .get(&quote_mint)
+            .ok_or_else(|| TradingVenueError::VaultNotFound(ErrorInfo::Pubkey(quote_mint)))?;
+
+        let base_price = self.checked_oracle_price(base_vault, &self.oracles)?;
+        let quote_price = self.checked_oracle_price(quote_vault, &self.oracles)?;
+
+        Ok(base_price as f64 / quote_price as f64)
+    }
+
+    /// A swap fills in full while its pre-fee output fits in the output
+    /// vault. The largest such input is estimated from the oracle prices,
+    /// then pinned to the exact atom against `compute_swap_math`, the same
+    /// check `quote` applies.
+    fn max_safe_input(
+        &self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+    ) -> Result<u64, TradingVenueError> {
+        if !self.initialized {
+            return Err(TradingVenueError::NotInitialized(ErrorInfo::StaticStr(
+                "venue not initialized",
+            )));
+        }
+        if input_mint == output_mint {
+            return Err(TradingVenueError::InvalidMint(output_mint.into()));
+        }
+        self.ensure_tradable(&input_mint)?;
+        self.ensure_tradable(&output_mint)?;
+
+        let vault_in = self
+            .vaults
+            .get(&input_mint)
+            .ok_or_else(|| TradingVenueError::VaultNotFound(input_mint.into()))?;
+        let vault_out = self
+            .vaults
+            .get(&output_mint)
+            .ok_or_else(|| TradingVenueError::VaultNotFound(output_mint.into()))?;
+        let decimals_in = self
+            .mints
+            .get(&input_mint)
+            .ok_or_else(|| TradingVenueError::InvalidMint(input_mint.into()))?
+            .decimals;
+        let decimals_out = self
+            .mints
+            .get(&output_mint)
+            .ok_or_else(|| TradingVenueError::InvalidMint(output_mint.into()))?
+            .decimals;
+        let price_in = self.checked_oracle_price(vault_in, &self.oracles)?;
+        let price_out = self.checked_oracle_price(vault_out, &self.oracles)?;
+
+        let in_token = self.get_token_by_mint(&input_mint)?;
+
+        let liquidity = vault_out.current_liquidity;
+        let fits = |amount_in: u64| {
+            compute_swap_math(
+                in_token.amount_after_transfer_fee(amount_in),
+                price_in,
+                price_out,
+                decimals_in,
+                decimals_out,
+                vault_in,
+                vault_out,
+                &self.treasury,
+            )
+            .is_ok()
+        };
+
+        // liquidity * price_out * 10^decimals_in / (price_in * 10^decimals_out)
+        let estimate = 10u128
+            .checked_pow(decimals_in as u32)
+            .and_then(|scale| {
+                (liquidity as u128)
+                    .checked_mul(price_out as u128)?
+                    .checked_mul(scale)
+            })
+            .zip(
+                10u128
+                    .checked_pow(decimals_out as u32)
+                    .and_then(|scale| (price_in as u128).checked_mul(scale)),
+            )
+            .map_or(u64::MAX, |(num, den)| {
+                u64::try_from(num / den).unwrap_or(u64::MAX)
+            });
+
+        // Gallop away from the estimate until the boundary is bracketed by
+        // `low` (fits) and `high` (does not), then bisect.
+        let (mut low, mut high);
+        let mut step: u64 = 1;
+        if fits(estimate) {
+            low = estimate;
+            loop {
+                let probe = low.saturating_add(step);
+                if probe == low {
+                    return Ok(low);
+                }
+                if !fits(probe) {
+                    high = probe;
+                    break;
+                }
+                low = probe;
+                step = step.saturating_mul(2);
+            }
+        } else {
+            high = estimate;
+            loop {
+                // Zero input always quotes in full, even when the swap math
+                // rejects every amount, so the search bottoms out there.
+                let probe = high.saturating_sub(step);
+                if probe == 0 || fits(probe) {
+                    low = probe;
+                    break;
+                }
+                high = probe;
+                step = step.saturating_mul(2);
+            }
+        }
+
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if fits(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(low)
+    }
+
+    /// ATAs are derived under each mint's own token program. The program
+    /// takes a single token program account, so both mints must share one.
+    ///
+    /// # Errors
+    /// - `OracleNotFound` if either mint is not in the registry
+    /// - `InvalidMint` if either mint is not loaded
+    /// - `UnsupportedVenue` if one mint is SPL Token and the other Token-2022
+    fn get_swap_accounts(
+        &self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
         user: Pubkey,
-    ) -> Result<Instruction, TradingVenueError> {
-        let user_in_ata = get_associated_token_address(&user, &request.input_mint);
-        let user_out_ata = get_associated_token_address(&user, &request.output_mint);
-
-        let treasury_pda = Pubkey::find_program_address(
-            &[OXEDIUM_SEED.as_bytes(), TREASURY_SEED.as_bytes()],
-            &OXEDIUM_AMM_PROGRAM_ID,
-        )
-        .0;
-
-        let treasury_in_ata = get_associated_token_address(&treasury_pda, &request.input_mint);
-        let treasury_out_ata = get_associated_token_address(&treasury_pda, &request.output_mint);
-
-        let vault_in = Pubkey::find_program_address(
-            &[VAULT_SEED.as_bytes(), request.input_mint.as_ref()],
-            &OXEDIUM_AMM_PROGRAM_ID,
-        )
-        .0;
-
-        let vault_out = Pubkey::find_program_address(
-            &[VAULT_SEED.as_bytes(), request.output_mint.as_ref()],
-            &OXEDIUM_AMM_PROGRAM_ID,
-        )
-        .0;
-
-        let oracle_in =
-            oracle_for_mint(&request.input_mint).ok_or(TradingVenueError::OracleNotFound)?;
-        let oracle_out =
-            oracle_for_mint(&request.output_mint).ok_or(TradingVenueError::OracleNotFound)?;
+    ) -> Result<Vec<AccountMeta>, TradingVenueError> {
+        let oracle_in = self
+            .oracle_for_mint(&input_mint)
+            .ok_or(TradingVenueError::OracleNotFound)?;
+        let oracle_out = self
+            .oracle_for_mint(&output_mint)
+            .ok_or(TradingVenueError::OracleNotFound)?;
+
+        let input_token = self.get_token_by_mint(&input_mint)?;
+        let output_token = self.get_token_by_mint(&output_mint)?;
+        let token_program = input_token.get_token_program();
+        if output_token.get_token_program() != token_program {
+            return Err(TradingVenueError::UnsupportedVenue(ErrorInfo::StaticStr(
+                "swap between SPL Token and Token-2022 mints",
+            )));
+        }
+
+        let user_in_ata = input_token.get_associated_token_address(&user);
+        let user_out_ata = output_token.get_associated_token_address(&user);
+
+        let treasury_pda = treasury_pda();
+
+        let treasury_in_ata = input_token.get_associated_token_address(&treasury_pda);
+        let treasury_out_ata = output_token.get_associated_token_address(&treasury_pda);
+
+        let vault_in = vault_pda(&input_mint);
+        let vault_out = vault_pda(&output_mint);
 
         let accounts = vec![
             AccountMeta::new(user, true),
-            AccountMeta::new_readonly(request.input_mint, false),
-            AccountMeta::new_readonly(request.output_mint, false),
+            AccountMeta::new_readonly(input_mint, false),
+            AccountMeta::new_readonly(output_mint, false),
             AccountMeta::new_readonly(oracle_in, false),
             AccountMeta::new_readonly(oracle_out, false),
             AccountMeta::new(user_in_ata, false),
@@ -350,26 +1320,98 @@ impl TradingVenue for OxediumAmmVenue {
             AccountMeta::new(treasury_in_ata, false),
             AccountMeta::new(treasury_out_ata, false),
             AccountMeta::new_readonly(spl_associated_token_account::ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(system_program::ID, false),
         ];
 
-        const DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
-        let mut data = Vec::with_capacity(8 + 16);
-        data.extend_from_slice(&DISCRIMINATOR);
+        Ok(accounts)
+    }
 
-        SwapIxData {
-            amount_in: request.amount,
-            min_amount_out: 0,
+    /// Program ids plus, for every tradable mint, the mint, its oracle, its
+    /// vault PDA, its token program, and the treasury's token account for it.
+    async fn get_lookup_table_keys(
+        &self,
+        _accounts_cache: Option<&dyn AccountsCache>,
+    ) -> Result<Vec<Pubkey>, TradingVenueError> {
+        let treasury_pda = treasury_pda();
+        let mut keys = vec![
+            OXEDIUM_AMM_PROGRAM_ID,
+            treasury_pda,
+            spl_associated_token_account::ID,
+            spl_token::ID,
+            system_program::ID,
+        ];
+
+        for token in &self.token_infos {
+            let oracle = self
+                .oracle_for_mint(&token.pubkey)
+                .ok_or(TradingVenueError::OracleNotFound)?;
+            keys.extend([
+                token.pubkey,
+                oracle,
+                vault_pda(&token.pubkey),
+                token.get_token_program(),
+                token.get_associated_token_address(&treasury_pda),
+            ]);
         }
-        .serialize(&mut data)
-        .map_err(|e| TradingVenueError::SerializationFailed(ErrorInfo::String(format!("{e:?}"))))?;
 
-        Ok(Instruction {
-            program_id: OXEDIUM_AMM_PROGRAM_ID,
-            accounts,
-            data,
-        })
+        // Registries may share an oracle between mints.
+        let mut seen = HashSet::new();
+        keys.retain(|key| seen.insert(*key));
+        Ok(keys)
+    }
+
+    /// Fails with `NotEnoughLiquidity` when the vaults cannot fill the full
+    /// `request.amount`; requote with the partial `amount` to swap less.
+    fn generate_swap_instruction(
+        &self,
+        request: QuoteRequest,
+        user: Pubkey,
+    ) -> Result<Instruction, TradingVenueError> {
+        let quote = self.quote(request.clone())?;
+        if quote.not_enough_liquidity {
+            return Err(TradingVenueError::NotEnoughLiquidity(ErrorInfo::Pubkey(
+                request.output_mint,
+            )));
+        }
+        let min_amount_out = quote.minimum_received(request.slippage_bps);
+
+        self.generate_swap_instruction_with_min(request, user, min_amount_out)
+    }
+
+    /// The swap, preceded by an idempotent create of the user's output ATA so
+    /// a first swap into a token does not fail on a missing account.
+    ///
+    /// With `create_treasury_atas` set, the treasury's input and output ATAs
+    /// are created the same way, funded by `user`. Each ATA is created under
+    /// its mint's token program.
+    fn generate_swap_instructions(
+        &self,
+        request: QuoteRequest,
+        user: Pubkey,
+    ) -> Result<Vec<Instruction>, TradingVenueError> {
+        let mut owners = vec![(user, request.output_mint)];
+        if self.create_treasury_atas {
+            let treasury_pda = treasury_pda();
+            owners.push((treasury_pda, request.input_mint));
+            owners.push((treasury_pda, request.output_mint));
+        }
+
+        let mut instructions = owners
+            .iter()
+            .map(|(owner, mint)| {
+                let token_program = self.get_token_by_mint(mint)?.get_token_program();
+                Ok(create_associated_token_account_idempotent(
+                    &user,
+                    owner,
+                    mint,
+                    &token_program,
+                ))
+            })
+            .collect::<Result<Vec<Instruction>, TradingVenueError>>()?;
+        instructions.push(self.generate_swap_instruction(request, user)?);
+
+        Ok(instructions)
     }
 
     fn decimals(&self) -> Result<Vec<i32>, TradingVenueError> {
@@ -385,4 +1427,16 @@ impl TradingVenue for OxediumAmmVenue {
     fn label(&self) -> String {
         self.protocol().into()
     }
+
+    /// `"{label} {market_id} {mint}/{mint}/..."` over the tradable mints, each
+    /// shortened to its first and last four characters.
+    fn market_label(&self) -> String {
+        let mints: Vec<String> = self
+            .token_infos
+            .iter()
+            .map(|token| short_pubkey(&token.pubkey))
+            .collect();
+
+        format!("{} {} {}", self.label(), self.market, mints.join("/"))
+    }
 }
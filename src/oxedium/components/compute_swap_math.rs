@@ -22,19 +22,24 @@ pub fn compute_swap_math(
     vault_out: &Vault,
     treasury: &Treasury,
 ) -> Result<SwapMathResult, OxediumVenueError> {
-    let swap_fee_bps = fees_setting(&vault_in, &vault_out);
+    let swap_fee_bps = fees_setting(&vault_in, &vault_out)?;
     let protocol_fee_bps = treasury.fee_bps;
 
     let raw_out = raw_amount_out(amount_in, decimals_in, decimals_out, price_in, price_out)?;
 
     let ten_percent_of_liquidity = vault_out.current_liquidity / 10; // 10%
     let adjusted_swap_fee_bps = if raw_out > ten_percent_of_liquidity {
-        swap_fee_bps * 10 // e.g., x10 fee
+        swap_fee_bps
+            .checked_mul(10) // e.g., x10 fee
+            .ok_or(OxediumVenueError::SwapMathError)?
     } else {
         swap_fee_bps
     };
 
-    if adjusted_swap_fee_bps + protocol_fee_bps > 10_000 {
+    let total_fee_bps = adjusted_swap_fee_bps
+        .checked_add(protocol_fee_bps)
+        .ok_or(OxediumVenueError::SwapMathError)?;
+    if total_fee_bps > 10_000 {
         return Err(OxediumVenueError::SwapMathError);
     }
 
@@ -42,7 +47,7 @@ pub fn compute_swap_math(
         calculate_fee_amount(raw_out, adjusted_swap_fee_bps, protocol_fee_bps)?;
 
     if vault_out.current_liquidity < (after_fee + lp_fee + protocol_fee) {
-        return Err(OxediumVenueError::SwapMathError);
+        return Err(OxediumVenueError::NotEnoughLiquidity);
     }
 
     Ok(SwapMathResult {
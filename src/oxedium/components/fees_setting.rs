@@ -1,4 +1,4 @@
-use crate::oxedium::states::Vault;
+use crate::oxedium::{states::Vault, utils::OxediumVenueError};
 
 /// Calculates the swap fee (in basis points) based on the liquidity imbalance
 /// between the input and output vaults.
@@ -15,26 +15,20 @@ use crate::oxedium::states::Vault;
 /// * `vault_out` - The destination vault for the swap
 ///
 /// # Returns
-/// * `u64` - The calculated total fee in basis points (bps)
+/// * `Result<u64, OxediumVenueError>` - The calculated total fee in basis points (bps),
+///   or `SwapMathError` for a vault with no initial liquidity or a base fee above 100%
 pub fn fees_setting(
     vault_in: &Vault,
     vault_out: &Vault,
-) -> u64 {
+) -> Result<u64, OxediumVenueError> {
     // Relative liquidity deltas in basis points (can be negative)
-    let delta_in_bps: i128 =
-        (vault_in.current_liquidity as i128 - vault_in.initial_liquidity as i128)
-            * 10_000
-            / vault_in.initial_liquidity as i128;
-
-    let delta_out_bps: i128 =
-        (vault_out.current_liquidity as i128 - vault_out.initial_liquidity as i128)
-            * 10_000
-            / vault_out.initial_liquidity as i128;
+    let delta_in_bps = liquidity_delta_bps(vault_in)?;
+    let delta_out_bps = liquidity_delta_bps(vault_out)?;
 
     // If the swap does not worsen relative imbalance,
     // apply only the base fee
     if delta_in_bps <= delta_out_bps {
-        return vault_out.base_fee;
+        return Ok(vault_out.base_fee);
     }
 
     // Absolute deviation of output vault liquidity from its initial value (0..10_000 bps)
@@ -55,8 +49,23 @@ pub fn fees_setting(
 
     // Final fee calculation:
     // base_fee + curved proportional increase up to MAX_FEE_BPS
-    vault_out.base_fee
-        + (MAX_FEE_BPS - vault_out.base_fee)
+    let headroom_bps = MAX_FEE_BPS
+        .checked_sub(vault_out.base_fee)
+        .ok_or(OxediumVenueError::SwapMathError)?;
+
+    Ok(vault_out.base_fee
+        + headroom_bps
             * curved_deviation_bps
-            / 10_000
+            / 10_000)
+}
+
+/// Deviation of `vault` liquidity from its initial value, in basis points.
+fn liquidity_delta_bps(vault: &Vault) -> Result<i128, OxediumVenueError> {
+    if vault.initial_liquidity == 0 {
+        return Err(OxediumVenueError::SwapMathError);
+    }
+
+    Ok((vault.current_liquidity as i128 - vault.initial_liquidity as i128)
+        * 10_000
+        / vault.initial_liquidity as i128)
 }
@@ -28,7 +28,7 @@ pub fn raw_amount_out(
     let amount_fp = amount_in
         .checked_mul(SCALE)
         .ok_or(OxediumVenueError::SwapMathError)?
-        .checked_div(10u128.pow(decimals_in as u32))
+        .checked_div(pow10(decimals_in)?)
         .ok_or(OxediumVenueError::SwapMathError)?;
 
     // 2. Convert input token amount into USD value (still fixed-point)
@@ -49,7 +49,7 @@ pub fn raw_amount_out(
     // 4. Convert fixed-point output into smallest output token units
     //    This is the ONLY place where rounding occurs
     let out = out_fp
-        .checked_mul(10u128.pow(decimals_out as u32))
+        .checked_mul(pow10(decimals_out)?)
         .ok_or(OxediumVenueError::SwapMathError)?
         .checked_div(SCALE)
         .ok_or(OxediumVenueError::SwapMathError)?;
@@ -57,3 +57,10 @@ pub fn raw_amount_out(
     // Convert back to u64
     u64::try_from(out).map_err(|_| OxediumVenueError::SwapMathError)
 }
+
+/// `10^decimals`, or `SwapMathError` if it does not fit in a `u128`.
+fn pow10(decimals: u8) -> Result<u128, OxediumVenueError> {
+    10u128
+        .checked_pow(decimals as u32)
+        .ok_or(OxediumVenueError::SwapMathError)
+}
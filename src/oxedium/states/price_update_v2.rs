@@ -2,7 +2,9 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_pubkey::Pubkey;
 use std::io;
 
-use crate::oxedium::utils::ANCHOR_DISCRIMINATOR_LEN;
+use crate::oxedium::utils::{
+    ANCHOR_DISCRIMINATOR_LEN, PRICE_UPDATE_V2_DISCRIMINATOR, has_discriminator,
+};
 
 #[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
 pub struct PriceFeedMessage {
@@ -46,6 +48,12 @@ impl PriceUpdateV2 {
     pub const LEN: usize = 32 + 1 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8;
 
     pub fn try_from_account_data(data: &[u8]) -> Result<Self, io::Error> {
+        if !has_discriminator(data, &PRICE_UPDATE_V2_DISCRIMINATOR) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Not a PriceUpdateV2 account",
+            ));
+        }
         let mut data = &data[ANCHOR_DISCRIMINATOR_LEN..];
         if data.len() < Self::LEN {
             return Err(io::Error::new(
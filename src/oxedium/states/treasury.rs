@@ -1,9 +1,22 @@
 use solana_pubkey::Pubkey;
 use borsh::{BorshDeserialize, BorshSerialize};
+use std::io;
+
+use crate::oxedium::utils::{ANCHOR_DISCRIMINATOR_LEN, TREASURY_DISCRIMINATOR, has_discriminator};
 
 #[derive(Clone, Copy, Debug, Default, BorshDeserialize, BorshSerialize)]
 pub struct Treasury {
     pub stoptap: bool,
     pub admin: Pubkey,
     pub fee_bps: u64
-}
\ No newline at end of file
+}
+
+impl Treasury {
+    /// Decode a treasury account, checking its discriminator.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, io::Error> {
+        if !has_discriminator(data, &TREASURY_DISCRIMINATOR) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a treasury account"));
+        }
+        Self::deserialize(&mut &data[ANCHOR_DISCRIMINATOR_LEN..])
+    }
+}
@@ -1,5 +1,8 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_pubkey::Pubkey;
+use std::io;
+
+use crate::oxedium::utils::{ANCHOR_DISCRIMINATOR_LEN, VAULT_DISCRIMINATOR, has_discriminator};
 
 #[derive(Clone, Copy, Debug, Default, BorshDeserialize, BorshSerialize)]
 pub struct Vault {
@@ -18,4 +21,21 @@ pub struct Vault {
     
     pub cumulative_yield_per_lp: u128,
     pub protocol_yield: u64
-}
\ No newline at end of file
+}
+
+impl Vault {
+    /// Borsh-serialized length, excluding the Anchor discriminator.
+    pub const LEN: usize = 8 + 1 + 8 + 32 + 32 + 8 + 32 + 8 + 8 + 8 + 16 + 8;
+
+    /// Decode a vault account, checking its discriminator and length.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, io::Error> {
+        if !has_discriminator(data, &VAULT_DISCRIMINATOR) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a vault account"));
+        }
+        let mut data = &data[ANCHOR_DISCRIMINATOR_LEN..];
+        if data.len() < Self::LEN {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Account data too short"));
+        }
+        Self::deserialize(&mut data)
+    }
+}
@@ -8,4 +8,7 @@ pub const TREASURY_SEED: &str = "treasury-seed";
 
 pub  const SCALE: u128 = 1_000_000_000_000;
 
+/// Exponent of the fixed-point oracle prices consumed by the swap math (1e8).
+pub const PRICE_EXPONENT: i32 = -8;
+
 pub const ANCHOR_DISCRIMINATOR_LEN: usize = 8;
@@ -0,0 +1,23 @@
+//! Anchor account discriminators for the accounts the Oxedium venue reads.
+//!
+//! Anchor prefixes every account with the first 8 bytes of
+//! `sha256("account:<TypeName>")`. Checking them before deserializing keeps
+//! an account of the wrong type from decoding into plausible garbage.
+
+use crate::oxedium::utils::ANCHOR_DISCRIMINATOR_LEN;
+
+/// `sha256("account:Vault")[..8]`
+pub const VAULT_DISCRIMINATOR: [u8; ANCHOR_DISCRIMINATOR_LEN] = [211, 8, 232, 43, 2, 152, 117, 119];
+
+/// `sha256("account:Treasury")[..8]`
+pub const TREASURY_DISCRIMINATOR: [u8; ANCHOR_DISCRIMINATOR_LEN] =
+    [238, 239, 123, 238, 89, 1, 168, 253];
+
+/// `sha256("account:PriceUpdateV2")[..8]`, from the Pyth receiver program.
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; ANCHOR_DISCRIMINATOR_LEN] =
+    [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Whether `data` starts with `discriminator`.
+pub fn has_discriminator(data: &[u8], discriminator: &[u8; ANCHOR_DISCRIMINATOR_LEN]) -> bool {
+    data.get(..ANCHOR_DISCRIMINATOR_LEN) == Some(discriminator.as_slice())
+}
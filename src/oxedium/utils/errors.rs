@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::trading_venue::error::{ErrorInfo, TradingVenueError};
+
 #[derive(Debug, Error)]
 pub enum OxediumVenueError {
     // ---------- Lifecycle ----------
@@ -40,3 +42,44 @@ pub enum OxediumVenueError {
     #[error("invalid argument: {0}")]
     InvalidArgument(&'static str),
 }
+
+/// Map each Oxedium math/state error onto the closest Titan error.
+///
+/// `NotEnoughLiquidity` is not an error for routing: `quote()` turns it into a
+/// partial fill with `not_enough_liquidity` set. Outside quoting it surfaces as
+/// `TradingVenueError::NotEnoughLiquidity`.
+impl From<OxediumVenueError> for TradingVenueError {
+    fn from(e: OxediumVenueError) -> Self {
+        match e {
+            OxediumVenueError::NotInitialized => {
+                TradingVenueError::NotInitialized(ErrorInfo::StaticStr("venue not initialized"))
+            }
+            OxediumVenueError::VaultNotFound => {
+                TradingVenueError::VaultNotFound(ErrorInfo::StaticStr("vault not found"))
+            }
+            OxediumVenueError::MintNotFound => {
+                TradingVenueError::MissingState(ErrorInfo::StaticStr("mint not found"))
+            }
+            OxediumVenueError::OracleNotFound => TradingVenueError::OracleNotFound,
+            OxediumVenueError::TreasuryNotFound => {
+                TradingVenueError::MissingState(ErrorInfo::StaticStr("treasury not found"))
+            }
+            OxediumVenueError::AccountNotFound => {
+                TradingVenueError::NoAccountFound(ErrorInfo::StaticStr("account not found"))
+            }
+            OxediumVenueError::DeserializationError => TradingVenueError::DeserializationError,
+            OxediumVenueError::SerializationError => {
+                TradingVenueError::SerializationFailed(ErrorInfo::StaticStr("instruction data"))
+            }
+            OxediumVenueError::NotEnoughLiquidity => {
+                TradingVenueError::NotEnoughLiquidity(ErrorInfo::StaticStr("vault liquidity"))
+            }
+            OxediumVenueError::SwapMathError => {
+                TradingVenueError::MathError(ErrorInfo::StaticStr("swap math error"))
+            }
+            e @ OxediumVenueError::InvalidArgument(_) => {
+                TradingVenueError::UnsupportedVenue(e.to_string().into())
+            }
+        }
+    }
+}
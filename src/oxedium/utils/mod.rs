@@ -1,5 +1,7 @@
 pub use constants::*;
+pub use discriminator::*;
 pub use errors::*;
 
 pub mod constants;
+pub mod discriminator;
 pub mod errors;
\ No newline at end of file
@@ -13,7 +13,8 @@
 //!    using exponential stepping with overflow protection.
 //!
 //! 2. **Binary refinement** (`refine_lower`, `refine_upper`)  
-//!    Narrows those rough intervals into precise boundaries.
+//!    Narrows those rough intervals into precise boundaries, to within a
+//!    configurable `Tolerance` (100 atoms by default).
 //!
 //! A quote is considered *valid* when:
 //! - The venue returns `Ok(QuoteResult)`
@@ -22,13 +23,173 @@
 //!
 //! This module is protocol-agnostic and works for any Titan-integrated AMM.
 
-use std::u64;
+use std::{
+    cell::{Cell, RefCell},
+    u64,
+};
 
-use crate::trading_venue::{QuoteResult, error::TradingVenueError};
+use ahash::AHashMap;
+
+use crate::trading_venue::{
+    QuoteResult,
+    error::{ErrorInfo, TradingVenueError},
+};
 
 /// Each step in exponential search is scaled by this factor.
 const SCALING_FACTOR: u64 = 2;
 
+/// Default refinement precision used by `find_boundaries`, in atoms.
+pub const DEFAULT_TOLERANCE_ATOMS: u64 = 100;
+
+/// Precision at which binary refinement stops.
+///
+/// - `Atoms(n)` — stop once the bracketing interval is at most `n` atoms wide
+/// - `Bps(n)` — stop once the interval is at most `n` basis points of its
+///   upper end, which scales with the magnitude of the bound
+///
+/// A plain `u64` converts into `Atoms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tolerance {
+    Atoms(u64),
+    Bps(u64),
+}
+
+impl Tolerance {
+    /// Maximum accepted interval width for a bracket whose upper end is `high`.
+    ///
+    /// Never below 1: a bracket of adjacent values already pins the boundary
+    /// exactly, and narrowing further cannot make progress.
+    fn width(self, high: u64) -> u64 {
+        let width = match self {
+            Tolerance::Atoms(atoms) => atoms,
+            Tolerance::Bps(bps) => (high as u128 * bps as u128 / 10_000) as u64,
+        };
+        width.max(1)
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance::Atoms(DEFAULT_TOLERANCE_ATOMS)
+    }
+}
+
+impl From<u64> for Tolerance {
+    fn from(atoms: u64) -> Self {
+        Tolerance::Atoms(atoms)
+    }
+}
+
+/// Options for `find_boundaries_with_config`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoundarySearchConfig {
+    /// Precision at which binary refinement stops.
+    pub tolerance: Tolerance,
+
+    /// Maximum number of distinct amounts passed to the quoting function.
+    ///
+    /// Once exhausted the search aborts with `BoundarySearchFailed`, giving a
+    /// hard latency ceiling for pools with expensive or misbehaving quotes.
+    /// `None` means unlimited.
+    pub max_probes: Option<usize>,
+
+    /// Number of evenly spaced interior points of `[lower, upper]` to quote
+    /// after refinement.
+    ///
+    /// The search assumes a single contiguous valid region; if any sampled
+    /// point is invalid that assumption is broken and the search fails with
+    /// `BoundarySearchFailed`. `0` disables verification.
+    pub verify_samples: usize,
+}
+
+/// Wrapper that refuses to call the quoting closure once `max_probes` calls
+/// have been made.
+///
+/// Refused probes return an error, which the search phases treat as an
+/// invalid quote; callers must consult `check()` to surface the exhaustion.
+struct ProbeBudget<'a, F> {
+    f: &'a F,
+    max_probes: Option<usize>,
+    probes: Cell<usize>,
+    exhausted: Cell<bool>,
+}
+
+impl<'a, F> ProbeBudget<'a, F>
+where
+    F: Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+{
+    fn new(f: &'a F, max_probes: Option<usize>) -> Self {
+        Self {
+            f,
+            max_probes,
+            probes: Cell::new(0),
+            exhausted: Cell::new(false),
+        }
+    }
+
+    fn quote(&self, amount: u64) -> Result<QuoteResult, TradingVenueError> {
+        if self
+            .max_probes
+            .is_some_and(|max_probes| self.probes.get() >= max_probes)
+        {
+            self.exhausted.set(true);
+            return Err(TradingVenueError::BoundarySearchFailed(
+                "Probe budget exhausted".into(),
+            ));
+        }
+
+        self.probes.set(self.probes.get() + 1);
+        (self.f)(amount)
+    }
+
+    fn check(&self) -> Result<(), TradingVenueError> {
+        if self.exhausted.get() {
+            return Err(TradingVenueError::BoundarySearchFailed(ErrorInfo::String(
+                format!(
+                    "Probe budget of {} quotes exhausted before the search converged",
+                    self.probes.get()
+                ),
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Memoizing wrapper around a quoting closure, keyed on the input amount.
+///
+/// The coarse and refinement phases probe several amounts more than once
+/// (e.g. the bracket endpoints for the invariant checks), so repeated probes
+/// are served from the memo. Errors are memoized too, so a repeated probe of
+/// a failing amount returns a clone of the original error.
+struct MemoizedQuote<'a, F> {
+    f: &'a F,
+    memo: RefCell<AHashMap<u64, Result<QuoteResult, TradingVenueError>>>,
+}
+
+impl<'a, F> MemoizedQuote<'a, F>
+where
+    F: Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+{
+    fn new(f: &'a F) -> Self {
+        Self {
+            f,
+            memo: RefCell::new(AHashMap::default()),
+        }
+    }
+
+    fn quote(&self, amount: u64) -> Result<QuoteResult, TradingVenueError> {
+        if let Some(hit) = self.memo.borrow().get(&amount) {
+            return hit.clone();
+        }
+
+        let result = (self.f)(amount);
+        self.memo.borrow_mut().insert(amount, result.clone());
+
+        result
+    }
+}
+
 /// Returns `true` if a quote is considered usable for routing.
 ///
 /// A quote is invalid if:
@@ -128,15 +289,32 @@ pub fn find_boundaries_coarse(
 /// This function performs binary search to find the *smallest* value that
 /// produces a valid quote.
 ///
-/// Searches until the interval is narrower than ~100 atoms.
+/// Searches until the interval is no wider than `tolerance`.
 ///
 /// # Errors
 /// Only returns errors from the provided quoting function.
 pub fn refine_lower(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+    low: u64,
+    high: u64,
+    tolerance: Tolerance,
+) -> Result<u64, TradingVenueError> {
+    refine_lower_with_quote(f, low, high, tolerance).map(|(bound, _)| bound)
+}
+
+/// Same as `refine_lower`, but also returns the quote at the refined bound.
+///
+/// The quote is the one already produced while searching, so no extra
+/// `quote()` call is made unless the starting `high` violated the invariant.
+///
+/// # Errors
+/// Only returns errors from the provided quoting function.
+pub fn refine_lower_with_quote(
     f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
     mut low: u64,
     mut high: u64,
-) -> Result<u64, TradingVenueError> {
+    tolerance: Tolerance,
+) -> Result<(u64, QuoteResult), TradingVenueError> {
     // These invariant checks should normally never trigger.
     let low_quote = f(low);
     let high_quote = f(high);
@@ -149,28 +327,33 @@ pub fn refine_lower(
         }
     }
 
-    match high_quote {
+    let mut high_result = match high_quote {
         Ok(result) => {
             if !valid_quote(&result) {
                 log::error!("The upper low is invalid; this contradicts the search invariant.");
             }
+            Some(result)
         }
         Err(e) => {
             log::error!(
                 "The upper low errored; this contradicts the search invariant: {:?}",
                 e
             );
+            None
         }
-    }
+    };
 
     // Binary search
-    while (high - low) > 100 {
-        let mid = high / 2 + low / 2;
+    // `low + (high - low) / 2` cannot overflow, and since the loop only runs
+    // while the bracket is at least 2 wide, `mid` is strictly inside it.
+    while (high - low) > tolerance.width(high) {
+        let mid = low + (high - low) / 2;
 
         match f(mid) {
             Ok(result) => {
                 if valid_quote(&result) {
                     high = mid;
+                    high_result = Some(result);
                 } else {
                     low = mid;
                 }
@@ -179,7 +362,12 @@ pub fn refine_lower(
         }
     }
 
-    Ok(high)
+    let high_result = match high_result {
+        Some(result) => result,
+        None => f(high)?,
+    };
+
+    Ok((high, high_result))
 }
 
 /// Refine the upper boundary via binary search.
@@ -192,32 +380,51 @@ pub fn refine_lower(
 ///
 /// This function finds the *largest* value that still produces a valid quote.
 ///
-/// Searches until the interval is narrower than ~100 atoms.
+/// Searches until the interval is no wider than `tolerance`.
 ///
 /// # Errors
 /// Only returns errors from the provided quoting function.
 pub fn refine_upper(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+    low: u64,
+    high: u64,
+    tolerance: Tolerance,
+) -> Result<u64, TradingVenueError> {
+    refine_upper_with_quote(f, low, high, tolerance).map(|(bound, _)| bound)
+}
+
+/// Same as `refine_upper`, but also returns the quote at the refined bound.
+///
+/// The quote is the one already produced while searching, so no extra
+/// `quote()` call is made unless the starting `low` violated the invariant.
+///
+/// # Errors
+/// Only returns errors from the provided quoting function.
+pub fn refine_upper_with_quote(
     f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
     mut low: u64,
     mut high: u64,
-) -> Result<u64, TradingVenueError> {
+    tolerance: Tolerance,
+) -> Result<(u64, QuoteResult), TradingVenueError> {
     let low_quote = f(low);
     let high_quote = f(high);
 
     // Sanity checks ― not usually hit
-    match low_quote {
+    let mut low_result = match low_quote {
         Ok(result) => {
             if !valid_quote(&result) {
                 log::error!("The upper low is invalid; this contradicts invariants.");
             }
+            Some(result)
         }
         Err(e) => {
             log::error!(
                 "The upper low errored; this contradicts invariants: {:?}",
                 e
             );
+            None
         }
-    }
+    };
 
     if let Ok(ref result) = high_quote {
         if valid_quote(result) && high != u64::MAX {
@@ -226,13 +433,16 @@ pub fn refine_upper(
     }
 
     // Binary search
-    while (high - low) > 100 {
-        let mid = high / 2 + low / 2;
+    // `low + (high - low) / 2` cannot overflow, and since the loop only runs
+    // while the bracket is at least 2 wide, `mid` is strictly inside it.
+    while (high - low) > tolerance.width(high) {
+        let mid = low + (high - low) / 2;
 
         match f(mid) {
             Ok(result) => {
                 if valid_quote(&result) {
                     low = mid;
+                    low_result = Some(result);
                 } else {
                     high = mid;
                 }
@@ -241,7 +451,12 @@ pub fn refine_upper(
         }
     }
 
-    Ok(low)
+    let low_result = match low_result {
+        Some(result) => result,
+        None => f(low)?,
+    };
+
+    Ok((low, low_result))
 }
 
 /// Unified boundary search.
@@ -252,7 +467,7 @@ pub fn refine_upper(
 /// - For all `x > upper_bound`, quoting is invalid
 ///
 /// The returned interval represents the **maximal valid input range** for the
-/// given pool and token pair.
+/// given pool and token pair, refined to within `DEFAULT_TOLERANCE_ATOMS`.
 ///
 /// # Errors
 /// - `BoundarySearchFailed` if the search collapses to a degenerate interval  
@@ -260,6 +475,79 @@ pub fn refine_upper(
 pub fn find_boundaries(
     f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
 ) -> Result<(u64, u64), TradingVenueError> {
+    find_boundaries_with_tolerance(f, Tolerance::default())
+}
+
+/// Unified boundary search with a caller-chosen refinement precision.
+///
+/// Behaves like `find_boundaries`, but binary refinement stops once each
+/// bracketing interval is within `tolerance`. Pass a `u64` for an absolute
+/// width in atoms, or `Tolerance::Bps` for a width relative to the bound.
+///
+/// # Errors
+/// Same as `find_boundaries`.
+pub fn find_boundaries_with_tolerance(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+    tolerance: impl Into<Tolerance>,
+) -> Result<(u64, u64), TradingVenueError> {
+    find_boundaries_with_config(
+        f,
+        BoundarySearchConfig {
+            tolerance: tolerance.into(),
+            ..Default::default()
+        },
+    )
+}
+
+/// Unified boundary search driven by a `BoundarySearchConfig`.
+///
+/// Behaves like `find_boundaries`, with the refinement precision and probe
+/// budget taken from `config`. Memoized repeat probes do not count against
+/// the budget.
+///
+/// # Errors
+/// - Same as `find_boundaries`
+/// - `BoundarySearchFailed` if `config.max_probes` is exhausted
+pub fn find_boundaries_with_config(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+    config: BoundarySearchConfig,
+) -> Result<(u64, u64), TradingVenueError> {
+    find_boundaries_with_quotes_and_config(f, config)
+        .map(|(lower_bound, _, upper_bound, _)| (lower_bound, upper_bound))
+}
+
+/// Quote `samples` evenly spaced points strictly inside `[lower, upper]` and
+/// fail if any of them is invalid.
+///
+/// # Errors
+/// - `BoundarySearchFailed` naming the first invalid sample
+pub fn verify_interval(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+    lower: u64,
+    upper: u64,
+    samples: usize,
+) -> Result<(), TradingVenueError> {
+    let width = upper.saturating_sub(lower) as u128;
+
+    for i in 1..=samples as u128 {
+        let x = lower + (width * i / (samples as u128 + 1)) as u64;
+
+        if !f(x).is_ok_and(|result| valid_quote(&result)) {
+            return Err(TradingVenueError::BoundarySearchFailed(ErrorInfo::String(
+                format!(
+                    "Quote at {x} inside [{lower}, {upper}] is invalid; valid region is not contiguous"
+                ),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the coarse search and reject degenerate or empty results.
+fn checked_coarse_bounds(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+) -> Result<(u64, u64, u64, u64), TradingVenueError> {
     let (lower_low, lower_high, upper_low, upper_high) = find_boundaries_coarse(f)?;
 
     // Degenerate interval: the entire domain is invalid.
@@ -276,8 +564,49 @@ pub fn find_boundaries(
         ));
     }
 
-    let lower_bound = refine_lower(f, lower_low, lower_high)?;
-    let upper_bound = refine_upper(f, upper_low, upper_high)?;
+    Ok((lower_low, lower_high, upper_low, upper_high))
+}
+
+/// Unified boundary search that also returns the quotes at both bounds.
+///
+/// Returns `(lower_bound, lower_quote, upper_bound, upper_quote)`, where each
+/// quote is the result of `f` at the corresponding bound. The quotes are
+/// reused from the search itself, sparing callers a second `quote()` call.
+///
+/// # Errors
+/// Same as `find_boundaries`.
+pub fn find_boundaries_with_quotes(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+) -> Result<(u64, QuoteResult, u64, QuoteResult), TradingVenueError> {
+    find_boundaries_with_quotes_and_config(f, BoundarySearchConfig::default())
+}
+
+/// Same as `find_boundaries_with_quotes`, driven by a `BoundarySearchConfig`
+/// like `find_boundaries_with_config`.
+///
+/// # Errors
+/// Same as `find_boundaries_with_config`.
+pub fn find_boundaries_with_quotes_and_config(
+    f: &impl Fn(u64) -> Result<QuoteResult, TradingVenueError>,
+    config: BoundarySearchConfig,
+) -> Result<(u64, QuoteResult, u64, QuoteResult), TradingVenueError> {
+    let budget = ProbeBudget::new(f, config.max_probes);
+    let budgeted = |x: u64| budget.quote(x);
+    let memo = MemoizedQuote::new(&budgeted);
+    let f = &|x: u64| memo.quote(x);
+
+    let coarse = checked_coarse_bounds(f);
+    budget.check()?;
+    let (lower_low, lower_high, upper_low, upper_high) = coarse?;
+
+    let lower = refine_lower_with_quote(f, lower_low, lower_high, config.tolerance);
+    let upper = refine_upper_with_quote(f, upper_low, upper_high, config.tolerance);
+    budget.check()?;
+    let ((lower_bound, lower_quote), (upper_bound, upper_quote)) = (lower?, upper?);
+
+    let verified = verify_interval(f, lower_bound, upper_bound, config.verify_samples);
+    budget.check()?;
+    verified?;
 
-    Ok((lower_bound, upper_bound))
+    Ok((lower_bound, lower_quote, upper_bound, upper_quote))
 }
@@ -11,7 +11,7 @@
 //! an error (e.g., a failing pubkey or message). Most variants use it so that
 //! callers can render meaningful context without allocating dynamically.
 
-use std::fmt::Display;
+use std::{fmt::Display, mem::discriminant};
 
 use solana_pubkey::Pubkey;
 use thiserror::Error;
@@ -103,7 +103,16 @@ impl Display for ErrorInfo {
 ///
 /// **Boundary search & quoting issues**  
 /// - `BoundarySearchFailed`  
-/// - `NoQuotableValue`
+/// - `NoQuotableValue`  
+/// - `NotEnoughLiquidity`
+///
+/// **Oracle issues**  
+/// - `OracleNotFound`  
+/// - `MissingOracle`  
+/// - `StaleOracle`  
+/// - `InsufficientOracleVerification`  
+/// - `OracleConfidenceTooWide`  
+/// - `InvalidOraclePrice`
 ///
 /// **Internal/unexpected issues**  
 /// - `SomethingWentWrong` (boxed error for unexpected failures)  
@@ -183,6 +192,10 @@ pub enum TradingVenueError {
     #[error("Boundary search failed: {0}")]
     NoQuotableValue(ErrorInfo),
 
+    /// The venue holds too little liquidity to fill the requested amount.
+    #[error("Not enough liquidity: {0}")]
+    NotEnoughLiquidity(ErrorInfo),
+
     /// Catch-all wrapper for unexpected boxed errors.
     #[error("Something went wrong: {0}")]
     SomethingWentWrong(Box<dyn std::error::Error>),
@@ -211,11 +224,243 @@ pub enum TradingVenueError {
     #[error("Account cache error: {0}")]
     AccountCacheError(#[from] AccountCacheError),
 
-    /// Oracle not found
+    /// No oracle is configured for a mint, i.e. the venue's mint-to-oracle
+    /// registry lacks it.
     #[error("Oracle not found")]
     OracleNotFound,
 
+    /// An oracle is configured but its account was not loaded into the
+    /// venue's state, e.g. because the fetch during `update_state` failed.
+    #[error("Oracle account not loaded: {0}")]
+    MissingOracle(ErrorInfo),
+
     /// Oracle not found
     #[error("Vault not found: {0}")]
-    VaultNotFound(ErrorInfo)
+    VaultNotFound(ErrorInfo),
+
+    /// Oracle price is older than the venue's configured maximum age.
+    #[error("Stale oracle price: {0}")]
+    StaleOracle(ErrorInfo),
+
+    /// Oracle price was posted with a weaker verification level than required.
+    #[error("Oracle price verification level is insufficient: {0}")]
+    InsufficientOracleVerification(ErrorInfo),
+
+    /// Oracle confidence interval is too wide relative to its price.
+    #[error("Oracle confidence interval too wide: {0}")]
+    OracleConfidenceTooWide(ErrorInfo),
+
+    /// Oracle price is zero or negative and cannot be used for pricing.
+    #[error("Invalid oracle price: {0}")]
+    InvalidOraclePrice(ErrorInfo),
+}
+
+/// Coarse classification of a `TradingVenueError`, letting routers decide
+/// whether to retry a venue, wait for fresh state, or skip it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// RPC, cache, or other I/O failures; retrying may succeed.
+    Infrastructure,
+    /// Missing, stale, or unparseable venue state; resolves on a later update.
+    State,
+    /// Arithmetic or numeric conversion failures for the given input.
+    Math,
+    /// Requests the venue cannot serve (mint, swap type, or venue).
+    Unsupported,
+    /// No quotable amount or liquidity for the requested range.
+    Liquidity,
+}
+
+impl TradingVenueError {
+    /// Classify this error for routing decisions.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            TradingVenueError::FailedToFetchMultipleAccountData
+            | TradingVenueError::FailedToFetchAccountData(_)
+            | TradingVenueError::CacheUnlockFailed
+            | TradingVenueError::AccountCacheError(_)
+            | TradingVenueError::SomethingWentWrong(_) => ErrorCategory::Infrastructure,
+            TradingVenueError::NoAccountFound(_)
+            | TradingVenueError::FromAccountError(_)
+            | TradingVenueError::DeserializationFailed(_)
+            | TradingVenueError::SerializationFailed(_)
+            | TradingVenueError::DeserializationError
+            | TradingVenueError::NotInitialized(_)
+            | TradingVenueError::MissingState(_)
+            | TradingVenueError::VaultNotFound(_)
+            | TradingVenueError::InactivePoolError(_, _)
+            | TradingVenueError::OracleNotFound
+            | TradingVenueError::MissingOracle(_)
+            | TradingVenueError::StaleOracle(_)
+            | TradingVenueError::InsufficientOracleVerification(_)
+            | TradingVenueError::OracleConfidenceTooWide(_)
+            | TradingVenueError::InvalidOraclePrice(_) => ErrorCategory::State,
+            TradingVenueError::CheckedMathError(_)
+            | TradingVenueError::MathError(_)
+            | TradingVenueError::DataConversionError(_)
+            | TradingVenueError::AmmMethodError(_) => ErrorCategory::Math,
+            TradingVenueError::InvalidMint(_)
+            | TradingVenueError::TokenInfoIndexError(_)
+            | TradingVenueError::ExactOutNotSupported
+            | TradingVenueError::UnsupportedVenue(_) => ErrorCategory::Unsupported,
+            TradingVenueError::BoundarySearchFailed(_)
+            | TradingVenueError::NoQuotableValue(_)
+            | TradingVenueError::NotEnoughLiquidity(_) => ErrorCategory::Liquidity,
+        }
+    }
+
+    /// The `ErrorInfo` payload carried by this variant, if any.
+    ///
+    /// Matched exhaustively so that new variants must declare their payload.
+    fn info(&self) -> Option<&ErrorInfo> {
+        match self {
+            TradingVenueError::NoAccountFound(info)
+            | TradingVenueError::FromAccountError(info)
+            | TradingVenueError::FailedToFetchAccountData(info)
+            | TradingVenueError::DeserializationFailed(info)
+            | TradingVenueError::SerializationFailed(info)
+            | TradingVenueError::NotInitialized(info)
+            | TradingVenueError::MissingState(info)
+            | TradingVenueError::InvalidMint(info)
+            | TradingVenueError::CheckedMathError(info)
+            | TradingVenueError::AmmMethodError(info)
+            | TradingVenueError::DataConversionError(info)
+            | TradingVenueError::BoundarySearchFailed(info)
+            | TradingVenueError::NoQuotableValue(info)
+            | TradingVenueError::NotEnoughLiquidity(info)
+            | TradingVenueError::UnsupportedVenue(info)
+            | TradingVenueError::MathError(info)
+            | TradingVenueError::VaultNotFound(info)
+            | TradingVenueError::MissingOracle(info)
+            | TradingVenueError::StaleOracle(info)
+            | TradingVenueError::InsufficientOracleVerification(info)
+            | TradingVenueError::OracleConfidenceTooWide(info)
+            | TradingVenueError::InvalidOraclePrice(info) => Some(info),
+            TradingVenueError::FailedToFetchMultipleAccountData
+            | TradingVenueError::CacheUnlockFailed
+            | TradingVenueError::ExactOutNotSupported
+            | TradingVenueError::SomethingWentWrong(_)
+            | TradingVenueError::TokenInfoIndexError(_)
+            | TradingVenueError::DeserializationError
+            | TradingVenueError::InactivePoolError(_, _)
+            | TradingVenueError::AccountCacheError(_)
+            | TradingVenueError::OracleNotFound => None,
+        }
+    }
+}
+
+/// Errors compare equal when they are the same variant with equal payloads.
+///
+/// Variants wrapping foreign errors (`SomethingWentWrong`, `AccountCacheError`)
+/// cannot be compared structurally and are compared by their rendered message.
+impl PartialEq for TradingVenueError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                TradingVenueError::SomethingWentWrong(a),
+                TradingVenueError::SomethingWentWrong(b),
+            ) => a.to_string() == b.to_string(),
+            (TradingVenueError::AccountCacheError(a), TradingVenueError::AccountCacheError(b)) => {
+                a.to_string() == b.to_string()
+            }
+            (
+                TradingVenueError::TokenInfoIndexError(a),
+                TradingVenueError::TokenInfoIndexError(b),
+            ) => a == b,
+            (
+                TradingVenueError::InactivePoolError(a, a_protocol),
+                TradingVenueError::InactivePoolError(b, b_protocol),
+            ) => a == b && a_protocol == b_protocol,
+            _ => discriminant(self) == discriminant(other) && self.info() == other.info(),
+        }
+    }
+}
+
+/// Errors are cloned variant for variant.
+///
+/// Variants wrapping foreign errors that are not `Clone` (`SomethingWentWrong`,
+/// and `AccountCacheError`'s RPC client error) are cloned by rendered message,
+/// matching how they compare under `PartialEq`.
+impl Clone for TradingVenueError {
+    fn clone(&self) -> Self {
+        match self {
+            TradingVenueError::NoAccountFound(info) => {
+                TradingVenueError::NoAccountFound(info.clone())
+            }
+            TradingVenueError::FromAccountError(info) => {
+                TradingVenueError::FromAccountError(info.clone())
+            }
+            TradingVenueError::FailedToFetchMultipleAccountData => {
+                TradingVenueError::FailedToFetchMultipleAccountData
+            }
+            TradingVenueError::FailedToFetchAccountData(info) => {
+                TradingVenueError::FailedToFetchAccountData(info.clone())
+            }
+            TradingVenueError::DeserializationFailed(info) => {
+                TradingVenueError::DeserializationFailed(info.clone())
+            }
+            TradingVenueError::SerializationFailed(info) => {
+                TradingVenueError::SerializationFailed(info.clone())
+            }
+            TradingVenueError::CacheUnlockFailed => TradingVenueError::CacheUnlockFailed,
+            TradingVenueError::NotInitialized(info) => {
+                TradingVenueError::NotInitialized(info.clone())
+            }
+            TradingVenueError::MissingState(info) => TradingVenueError::MissingState(info.clone()),
+            TradingVenueError::InvalidMint(info) => TradingVenueError::InvalidMint(info.clone()),
+            TradingVenueError::CheckedMathError(info) => {
+                TradingVenueError::CheckedMathError(info.clone())
+            }
+            TradingVenueError::AmmMethodError(info) => {
+                TradingVenueError::AmmMethodError(info.clone())
+            }
+            TradingVenueError::ExactOutNotSupported => TradingVenueError::ExactOutNotSupported,
+            TradingVenueError::DataConversionError(info) => {
+                TradingVenueError::DataConversionError(info.clone())
+            }
+            TradingVenueError::BoundarySearchFailed(info) => {
+                TradingVenueError::BoundarySearchFailed(info.clone())
+            }
+            TradingVenueError::NoQuotableValue(info) => {
+                TradingVenueError::NoQuotableValue(info.clone())
+            }
+            TradingVenueError::NotEnoughLiquidity(info) => {
+                TradingVenueError::NotEnoughLiquidity(info.clone())
+            }
+            TradingVenueError::SomethingWentWrong(e) => {
+                TradingVenueError::SomethingWentWrong(e.to_string().into())
+            }
+            TradingVenueError::UnsupportedVenue(info) => {
+                TradingVenueError::UnsupportedVenue(info.clone())
+            }
+            TradingVenueError::TokenInfoIndexError(index) => {
+                TradingVenueError::TokenInfoIndexError(*index)
+            }
+            TradingVenueError::MathError(info) => TradingVenueError::MathError(info.clone()),
+            TradingVenueError::DeserializationError => TradingVenueError::DeserializationError,
+            TradingVenueError::InactivePoolError(pool, protocol) => {
+                TradingVenueError::InactivePoolError(*pool, *protocol)
+            }
+            TradingVenueError::AccountCacheError(e) => {
+                TradingVenueError::AccountCacheError(e.clone())
+            }
+            TradingVenueError::OracleNotFound => TradingVenueError::OracleNotFound,
+            TradingVenueError::MissingOracle(info) => {
+                TradingVenueError::MissingOracle(info.clone())
+            }
+            TradingVenueError::VaultNotFound(info) => {
+                TradingVenueError::VaultNotFound(info.clone())
+            }
+            TradingVenueError::StaleOracle(info) => TradingVenueError::StaleOracle(info.clone()),
+            TradingVenueError::InsufficientOracleVerification(info) => {
+                TradingVenueError::InsufficientOracleVerification(info.clone())
+            }
+            TradingVenueError::OracleConfidenceTooWide(info) => {
+                TradingVenueError::OracleConfidenceTooWide(info.clone())
+            }
+            TradingVenueError::InvalidOraclePrice(info) => {
+                TradingVenueError::InvalidOraclePrice(info.clone())
+            }
+        }
+    }
 }
@@ -12,21 +12,38 @@
 pub mod bounds;
 pub mod error;
 pub mod protocol;
+pub mod quote_cache;
+pub mod refresh;
+#[cfg(feature = "serde")]
+pub mod serde_pubkey;
+pub mod slippage;
 pub mod token_info;
+pub mod tx;
+
+use std::collections::{HashMap, HashSet};
 
 use async_trait::async_trait;
 use solana_account::Account;
-use solana_instruction::Instruction;
+use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 
 use crate::{
-    account_caching::AccountsCache,
+    account_caching::{AccountsCache, static_cache::StaticAccountsCache},
     trading_venue::{
-        bounds::find_boundaries, error::TradingVenueError, protocol::PoolProtocol,
+        bounds::{find_boundaries, find_boundaries_with_quotes},
+        error::{ErrorInfo, TradingVenueError},
+        protocol::PoolProtocol,
+        slippage::min_amount_with_slippage,
         token_info::TokenInfo,
     },
 };
 
+/// Compute units requested by the default `compute_unit_hint()`.
+pub const DEFAULT_COMPUTE_UNIT_HINT: u32 = 200_000;
+
+/// Fraction of one base token quoted by the default `spot_price()` probe.
+pub const SPOT_PRICE_PROBE_UI_FRACTION: u64 = 1_000;
+
 /// Describes which type of swap the user is performing.
 ///
 /// * `ExactIn`  — The user specifies exactly how many input atoms they want
@@ -38,7 +55,8 @@ use crate::{
 ///
 /// **Warning:** Titan currently only supports `ExactIn`. Implementers *must*
 /// support `ExactIn`, and may optionally support `ExactOut` for future use.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SwapType {
     ExactIn,
     ExactOut,
@@ -48,11 +66,14 @@ pub enum SwapType {
 ///
 /// All amounts are denominated in integer atom units (not scaled to UI decimals).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuoteRequest {
     /// Mint of the token the user is providing.
+    #[cfg_attr(feature = "serde", serde(with = "crate::trading_venue::serde_pubkey"))]
     pub input_mint: Pubkey,
 
     /// Mint of the token the user expects to receive.
+    #[cfg_attr(feature = "serde", serde(with = "crate::trading_venue::serde_pubkey"))]
     pub output_mint: Pubkey,
 
     /// Amount of *input* or *output* atoms, depending on `swap_type`.
@@ -63,18 +84,143 @@ pub struct QuoteRequest {
     /// Titan currently calls venues only with `ExactIn`, but venues should
     /// not panic if `ExactOut` is provided.
     pub swap_type: SwapType,
+
+    /// Slippage tolerance in basis points applied when building the swap
+    /// instruction (e.g. 50 → 0.5%).
+    ///
+    /// Quoting ignores this value; it only affects the minimum output
+    /// enforced on-chain.
+    pub slippage_bps: u64,
+}
+
+impl QuoteRequest {
+    /// Construct a request with zero slippage tolerance.
+    pub fn new(input_mint: Pubkey, output_mint: Pubkey, amount: u64, swap_type: SwapType) -> Self {
+        Self {
+            input_mint,
+            output_mint,
+            amount,
+            swap_type,
+            slippage_bps: 0,
+        }
+    }
+
+    /// Start building a request; see `QuoteRequestBuilder`.
+    pub fn builder() -> QuoteRequestBuilder {
+        QuoteRequestBuilder::default()
+    }
+}
+
+/// Step-by-step constructor for `QuoteRequest`.
+///
+/// Both mints are required. The swap type defaults to `ExactIn`, and the
+/// amount and slippage default to zero.
+#[derive(Debug, Clone, Default)]
+pub struct QuoteRequestBuilder {
+    input_mint: Option<Pubkey>,
+    output_mint: Option<Pubkey>,
+    amount: u64,
+    swap_type: Option<SwapType>,
+    slippage_bps: u64,
+}
+
+impl QuoteRequestBuilder {
+    pub fn input_mint(mut self, input_mint: Pubkey) -> Self {
+        self.input_mint = Some(input_mint);
+        self
+    }
+
+    pub fn output_mint(mut self, output_mint: Pubkey) -> Self {
+        self.output_mint = Some(output_mint);
+        self
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// Treat `amount` as the exact number of input atoms.
+    pub fn exact_in(mut self) -> Self {
+        self.swap_type = Some(SwapType::ExactIn);
+        self
+    }
+
+    /// Treat `amount` as the exact number of output atoms.
+    pub fn exact_out(mut self) -> Self {
+        self.swap_type = Some(SwapType::ExactOut);
+        self
+    }
+
+    pub fn slippage_bps(mut self, slippage_bps: u64) -> Self {
+        self.slippage_bps = slippage_bps;
+        self
+    }
+
+    /// Finish the request.
+    ///
+    /// # Errors
+    /// - `InvalidMint` if either mint was never set
+    pub fn build(self) -> Result<QuoteRequest, TradingVenueError> {
+        let input_mint =
+            self.input_mint
+                .ok_or(TradingVenueError::InvalidMint(ErrorInfo::StaticStr(
+                    "QuoteRequest input_mint not set",
+                )))?;
+        let output_mint =
+            self.output_mint
+                .ok_or(TradingVenueError::InvalidMint(ErrorInfo::StaticStr(
+                    "QuoteRequest output_mint not set",
+                )))?;
+
+        Ok(QuoteRequest {
+            input_mint,
+            output_mint,
+            amount: self.amount,
+            swap_type: self.swap_type.unwrap_or(SwapType::ExactIn),
+            slippage_bps: self.slippage_bps,
+        })
+    }
+}
+
+/// Components of the fee charged on a swap, in input-mint atoms.
+///
+/// Components a venue does not charge are zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeBreakdown {
+    /// Fee retained by the protocol (e.g. a treasury cut).
+    pub protocol_fee: u64,
+
+    /// Fee paid to liquidity providers.
+    pub lp_fee: u64,
+
+    /// Token-2022 transfer fees withheld by the mint(s).
+    pub transfer_fee: u64,
+}
+
+impl FeeBreakdown {
+    /// Sum of all components.
+    pub fn total(&self) -> u64 {
+        self.protocol_fee
+            .saturating_add(self.lp_fee)
+            .saturating_add(self.transfer_fee)
+    }
 }
 
 /// A result returned from a venue’s `quote()` implementation.
 ///
 /// This describes how much of the input would be consumed and how much
 /// output would be produced, based on current pool state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuoteResult {
     /// Mint of the token the user provided.
+    #[cfg_attr(feature = "serde", serde(with = "crate::trading_venue::serde_pubkey"))]
     pub input_mint: Pubkey,
 
     /// Mint of the token the user receives.
+    #[cfg_attr(feature = "serde", serde(with = "crate::trading_venue::serde_pubkey"))]
     pub output_mint: Pubkey,
 
     /// Actual amount of input atoms that would be consumed.
@@ -83,6 +229,18 @@ pub struct QuoteResult {
     /// Expected number of output atoms produced by the venue.
     pub expected_output: u64,
 
+    /// Total fee charged on the swap, denominated in input-mint atoms.
+    ///
+    /// Equal to `fees.total()`.
+    pub fee_amount: u64,
+
+    /// Per-component split of `fee_amount`.
+    pub fees: FeeBreakdown,
+
+    /// Relative deviation of the execution price from the venue's spot/mid
+    /// price, in basis points. Zero-input quotes report `0`.
+    pub price_impact_bps: u32,
+
     /// Indicates whether the pool has insufficient liquidity to consume the full input.
     ///
     /// For example, if a pool only has enough liquidity for half of the provided
@@ -90,6 +248,15 @@ pub struct QuoteResult {
     pub not_enough_liquidity: bool,
 }
 
+impl QuoteResult {
+    /// Lowest output the user accepts at `slippage_bps` below `expected_output`.
+    ///
+    /// Saturates to zero at or above 10_000 bps.
+    pub fn minimum_received(&self, slippage_bps: u64) -> u64 {
+        min_amount_with_slippage(self.expected_output, slippage_bps)
+    }
+}
+
 /// A convenience trait for converting on-chain accounts into structured pool/venue state.
 ///
 /// Implementers are responsible for performing any deserialization necessary
@@ -103,20 +270,6 @@ pub trait FromAccount {
         Self: Sized;
 }
 
-/// Trait allowing a venue to declare which address-lookup table (ALT) keys
-/// it requires for transaction construction.
-///
-/// Implementers should return all additional keys (besides swap accounts)
-/// that must be included in the ALT in order to successfully compress swaps.
-#[async_trait]
-pub trait AddressLookupTableTrait {
-    /// Return a list of pubkeys that should be inserted into an address lookup table.
-    async fn get_lookup_table_keys(
-        &self,
-        accounts_cache: Option<&dyn AccountsCache>,
-    ) -> Result<Vec<Pubkey>, TradingVenueError>;
-}
-
 /// Primary trait describing an AMM or trading venue integrated with Titan.
 ///
 /// Any AMM, orderbook, or custom liquidity engine must implement this trait
@@ -129,12 +282,45 @@ pub trait TradingVenue {
     /// are missing required on-chain accounts.
     fn initialized(&self) -> bool;
 
+    /// Whether the venue currently accepts swaps.
+    ///
+    /// Distinct from `initialized()`: a loaded pool may still be paused,
+    /// withdraw-only, or otherwise unable to swap. Defaults to `initialized()`
+    /// and `meets_liquidity_threshold()` for venues without such states.
+    fn is_tradable(&self) -> bool {
+        self.initialized() && self.meets_liquidity_threshold()
+    }
+
+    /// Whether the venue's reserves clear its configured liquidity floor, so
+    /// routers can skip dust pools.
+    ///
+    /// Defaults to `true` for venues without a configurable floor.
+    fn meets_liquidity_threshold(&self) -> bool {
+        true
+    }
+
     /// The main program ID for the venue.
     fn program_id(&self) -> Pubkey;
 
     /// All additional program IDs this venue depends on (e.g. SPL Token program).
     fn program_dependencies(&self) -> Vec<Pubkey>;
 
+    /// Every executable program a swap on this venue touches, directly or
+    /// through CPI, so harnesses such as LiteSVM can load them all.
+    ///
+    /// The default combines `program_id()` with `program_dependencies()`;
+    /// venues should override it to add programs reached only through CPI,
+    /// such as the SPL Token program.
+    fn required_programs(&self) -> Vec<Pubkey> {
+        let mut programs = vec![self.program_id()];
+        for program in self.program_dependencies() {
+            if !programs.contains(&program) {
+                programs.push(program);
+            }
+        }
+        programs
+    }
+
     /// Unique identifier for the market/pool instance.
     fn market_id(&self) -> Pubkey;
 
@@ -145,6 +331,34 @@ pub trait TradingVenue {
         Ok(self.get_token_info().iter().map(|x| x.pubkey).collect())
     }
 
+    /// The two tradable mints of a two-token venue, in `get_token_info()`
+    /// order.
+    ///
+    /// # Errors
+    /// - `UnsupportedVenue` if the venue does not trade exactly two mints
+    fn token_pair(&self) -> Result<(Pubkey, Pubkey), TradingVenueError> {
+        match self.tradable_mints()?.as_slice() {
+            [token_a, token_b] => Ok((*token_a, *token_b)),
+            mints => Err(TradingVenueError::UnsupportedVenue(ErrorInfo::String(
+                format!("expected a token pair, venue trades {} mints", mints.len()),
+            ))),
+        }
+    }
+
+    /// Every ordered `(tkn_in_ind, tkn_out_ind)` pair of distinct indices
+    /// into `get_token_info()`, i.e. each direction a multi-token venue can
+    /// be quoted and bounded in.
+    fn all_pairs(&self) -> Vec<(u8, u8)> {
+        let len = self.get_token_info().len().min(u8::MAX as usize + 1);
+        (0..len)
+            .flat_map(|i| {
+                (0..len)
+                    .filter(move |&j| j != i)
+                    .map(move |j| (i as u8, j as u8))
+            })
+            .collect()
+    }
+
     /// Return the decimals for each tradable token.
     fn decimals(&self) -> Result<Vec<i32>, TradingVenueError> {
         Ok(self.get_token_info().iter().map(|x| x.decimals).collect())
@@ -153,6 +367,14 @@ pub trait TradingVenue {
     /// Return fixed token metadata for this venue (mint + decimals).
     fn get_token_info(&self) -> &[TokenInfo];
 
+    /// Current liquidity reserves as `(mint, atoms)` pairs.
+    ///
+    /// The default implementation returns an empty list for venues that
+    /// cannot report reserves.
+    fn reserves(&self) -> Result<Vec<(Pubkey, u64)>, TradingVenueError> {
+        Ok(Vec::new())
+    }
+
     /// Fetch a single token by index.
     ///
     /// Returns an error if the index is out of bounds.
@@ -162,6 +384,16 @@ pub trait TradingVenue {
             .ok_or(TradingVenueError::TokenInfoIndexError(i))
     }
 
+    /// Fetch a single token by mint.
+    ///
+    /// Returns `InvalidMint` if the venue does not trade `mint`.
+    fn get_token_by_mint(&self, mint: &Pubkey) -> Result<&TokenInfo, TradingVenueError> {
+        self.get_token_info()
+            .iter()
+            .find(|token| token.pubkey == *mint)
+            .ok_or(TradingVenueError::InvalidMint(mint.into()))
+    }
+
     /// Identify which protocol type this venue is (e.g. Raydium, Orca, Phoenix).
     fn protocol(&self) -> PoolProtocol;
 
@@ -170,9 +402,20 @@ pub trait TradingVenue {
         self.protocol().into()
     }
 
+    /// A human-readable name for this market, for logs and dashboards.
+    ///
+    /// Defaults to `"{label} {market_id}"`; venues may override it to name
+    /// the traded tokens as well.
+    fn market_label(&self) -> String {
+        format!("{} {}", self.label(), self.market_id())
+    }
+
     /// Returns the minimal set of pubkeys required to update venue state.
     ///
     /// Titan will prefetch these accounts before calling `update_state()`.
+    ///
+    /// The order should be deterministic across calls so that caching and
+    /// request logs are reproducible.
     fn get_required_pubkeys_for_update(&self) -> Result<Vec<Pubkey>, TradingVenueError>;
 
     /// Update the venue's internal state from the provided account cache.
@@ -181,6 +424,50 @@ pub trait TradingVenue {
     /// orderbooks, or other relevant on-chain state.
     async fn update_state(&mut self, cache: &dyn AccountsCache) -> Result<(), TradingVenueError>;
 
+    /// Update the venue's internal state from accounts already in hand,
+    /// bypassing any `AccountsCache`.
+    ///
+    /// Intended for backtesting and deterministic simulation. `accounts`
+    /// should hold the keys from `get_required_pubkeys_for_update()`; keys it
+    /// lacks are treated as missing accounts, exactly as in `update_state()`.
+    ///
+    /// The default serves `accounts` through a `StaticAccountsCache` and
+    /// drives `update_state()` to completion on the calling thread. Venues
+    /// whose `update_state()` awaits anything besides the cache must override
+    /// it.
+    fn update_state_from_accounts(
+        &mut self,
+        accounts: &HashMap<Pubkey, Account>,
+    ) -> Result<(), TradingVenueError> {
+        let cache: StaticAccountsCache = accounts
+            .iter()
+            .map(|(pubkey, account)| (*pubkey, account.clone()))
+            .collect();
+
+        futures::executor::block_on(self.update_state(&cache))
+    }
+
+    /// Slot at which the venue's cached state was last refreshed, if known.
+    ///
+    /// Lets the router skip or deprioritize venues that have not updated
+    /// recently. `None` means the venue has not been updated (or does not
+    /// track it).
+    fn last_updated_slot(&self) -> Option<u64> {
+        None
+    }
+
+    /// The venue's swap fee in basis points, for display and cross-venue
+    /// comparison.
+    ///
+    /// Implementations return `NotInitialized` before state has been loaded.
+    /// The default returns `UnsupportedVenue`, for venues without a single
+    /// swap fee.
+    fn fee_bps(&self) -> Result<u32, TradingVenueError> {
+        Err(TradingVenueError::UnsupportedVenue(ErrorInfo::StaticStr(
+            "venue does not report fee_bps",
+        )))
+    }
+
     /// Compute a quote for the given swap parameters.
     ///
     /// **Implementer requirement:** the venue **must** handle zero input amounts
@@ -188,38 +475,369 @@ pub trait TradingVenue {
     /// quotes.
     fn quote(&self, request: QuoteRequest) -> Result<QuoteResult, TradingVenueError>;
 
+    /// Compute a quote, first reading any state the venue prices from at
+    /// quote time (e.g. an oracle) through `cache`.
+    ///
+    /// The default ignores `cache` and calls `quote`. Oracle-driven venues can
+    /// override this to quote against fresh prices instead of the ones
+    /// captured by the last `update_state`; pool math stays in `quote`.
+    async fn quote_async(
+        &self,
+        _cache: &dyn AccountsCache,
+        request: QuoteRequest,
+    ) -> Result<QuoteResult, TradingVenueError> {
+        self.quote(request)
+    }
+
+    /// Compute quotes for a batch of requests, returning one result per request
+    /// in the same order.
+    ///
+    /// The default implementation calls `quote()` for each request. Venues may
+    /// override this to amortize per-call setup or vectorize their swap math,
+    /// but must return the same results as individual `quote()` calls.
+    fn quote_many(&self, requests: &[QuoteRequest]) -> Vec<Result<QuoteResult, TradingVenueError>> {
+        requests
+            .iter()
+            .map(|request| self.quote(request.clone()))
+            .collect()
+    }
+
+    /// Output of swapping `amount` of `a` into `b` and the proceeds straight
+    /// back into `a`, both as `ExactIn` quotes against the current state.
+    ///
+    /// The second leg does not see the first leg's effect on the pool. A
+    /// healthy venue returns slightly less than `amount` because of fees; a
+    /// larger result points to mispriced state worth an arbitrage check.
+    fn round_trip_quote(
+        &self,
+        a: Pubkey,
+        b: Pubkey,
+        amount: u64,
+    ) -> Result<u64, TradingVenueError> {
+        let there = self.quote(QuoteRequest::new(a, b, amount, SwapType::ExactIn))?;
+        let back = self.quote(QuoteRequest::new(
+            b,
+            a,
+            there.expected_output,
+            SwapType::ExactIn,
+        ))?;
+        Ok(back.expected_output)
+    }
+
+    /// Instantaneous price of token `base_ind` denominated in token `quote_ind`,
+    /// in UI units (i.e. adjusted for both tokens' decimals).
+    ///
+    /// The default implementation quotes `SPOT_PRICE_PROBE_UI_FRACTION` of one
+    /// base token and divides, so it includes fees. Venues that can read a
+    /// mid price directly from their state (reserves, oracles) should override it.
+    ///
+    /// # Errors
+    /// - `NoQuotableValue` if the probe quote produces no output (e.g. an empty pool)
+    fn spot_price(&self, base_ind: u8, quote_ind: u8) -> Result<f64, TradingVenueError> {
+        let base = self.get_token(base_ind as usize)?;
+        let quote = self.get_token(quote_ind as usize)?;
+
+        let probe = (10u64.saturating_pow(base.decimals.max(0) as u32)
+            / SPOT_PRICE_PROBE_UI_FRACTION)
+            .max(1);
+        let result = self.quote(QuoteRequest::new(
+            base.pubkey,
+            quote.pubkey,
+            probe,
+            SwapType::ExactIn,
+        ))?;
+
+        if result.amount == 0 || result.expected_output == 0 {
+            return Err(TradingVenueError::NoQuotableValue(
+                "spot price probe produced no output".into(),
+            ));
+        }
+
+        let output = result.expected_output as f64 / 10f64.powi(quote.decimals);
+        let input = result.amount as f64 / 10f64.powi(base.decimals);
+        Ok(output / input)
+    }
+
+    /// How many `quote` atoms one `base` atom is currently worth.
+    ///
+    /// Unlike `spot_price`, this takes mints rather than token indices and is
+    /// expressed in raw atoms, which makes it directly comparable across
+    /// venues listing the same pair. The default rescales `spot_price` by the
+    /// tokens' decimals.
+    ///
+    /// # Errors
+    /// - `InvalidMint` if the venue does not trade `base` or `quote`
+    fn price_ratio(&self, base: &Pubkey, quote: &Pubkey) -> Result<f64, TradingVenueError> {
+        let base_ind = token_index(self, base)?;
+        let quote_ind = token_index(self, quote)?;
+
+        let ui_price = self.spot_price(base_ind, quote_ind)?;
+        let decimals_diff = self.get_token(quote_ind as usize)?.decimals
+            - self.get_token(base_ind as usize)?.decimals;
+        Ok(ui_price * 10f64.powi(decimals_diff))
+    }
+
+    /// Return the accounts a swap between `input_mint` and `output_mint` for
+    /// `user` touches, in instruction order.
+    ///
+    /// The account set does not depend on the swap amount, so this is cheap
+    /// to call for address lookup table packing or simulation setup.
+    /// `generate_swap_instruction` must use exactly these accounts.
+    ///
+    /// The default returns `UnsupportedVenue`, which also makes the helpers
+    /// built on it (`swap_account_keys`, `writable_swap_accounts`, and
+    /// `get_lookup_table_keys`) unavailable.
+    fn get_swap_accounts(
+        &self,
+        _input_mint: Pubkey,
+        _output_mint: Pubkey,
+        _user: Pubkey,
+    ) -> Result<Vec<AccountMeta>, TradingVenueError> {
+        Err(TradingVenueError::UnsupportedVenue(ErrorInfo::StaticStr(
+            "venue does not list swap accounts",
+        )))
+    }
+
+    /// Return every pubkey a swap for `request` by `user` references: the
+    /// swap accounts plus the venue's program id, without duplicates.
+    ///
+    /// Titan uses this to prefetch or seed simulations without building the
+    /// instruction.
+    fn swap_account_keys(
+        &self,
+        request: &QuoteRequest,
+        user: Pubkey,
+    ) -> Result<Vec<Pubkey>, TradingVenueError> {
+        let mut keys: Vec<Pubkey> = self
+            .get_swap_accounts(request.input_mint, request.output_mint, user)?
+            .into_iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        keys.push(self.program_id());
+
+        let mut seen = HashSet::new();
+        keys.retain(|key| seen.insert(*key));
+        Ok(keys)
+    }
+
+    /// Return the pubkeys a swap for `request` by `user` writes to, i.e. the
+    /// writable metas of `get_swap_accounts`.
+    ///
+    /// Callers can use this to snapshot only the accounts a simulated swap
+    /// modifies.
+    fn writable_swap_accounts(
+        &self,
+        request: &QuoteRequest,
+        user: Pubkey,
+    ) -> Result<Vec<Pubkey>, TradingVenueError> {
+        Ok(self
+            .get_swap_accounts(request.input_mint, request.output_mint, user)?
+            .into_iter()
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect())
+    }
+
+    /// Return the keys that should be inserted into an address lookup table
+    /// (ALT) to compress swaps on this venue.
+    ///
+    /// The default collects the venue's programs plus every swap account that
+    /// does not depend on the user, found by comparing the swap accounts of two
+    /// placeholder users across all tradable pairs. Venues may override this
+    /// with a cheaper or more complete list; `accounts_cache` is available for
+    /// venues whose keys must be read from on-chain state.
+    async fn get_lookup_table_keys(
+        &self,
+        _accounts_cache: Option<&dyn AccountsCache>,
+    ) -> Result<Vec<Pubkey>, TradingVenueError> {
+        let mut keys = vec![self.program_id()];
+        keys.extend(self.program_dependencies());
+
+        let user = Pubkey::new_from_array([1; 32]);
+        let other_user = Pubkey::new_from_array([2; 32]);
+        let mints = self.tradable_mints()?;
+        for input_mint in &mints {
+            for output_mint in mints.iter().filter(|mint| *mint != input_mint) {
+                let other = self.get_swap_accounts(*input_mint, *output_mint, other_user)?;
+                keys.extend(
+                    self.get_swap_accounts(*input_mint, *output_mint, user)?
+                        .into_iter()
+                        .map(|meta| meta.pubkey)
+                        .filter(|key| other.iter().any(|meta| meta.pubkey == *key)),
+                );
+            }
+        }
+
+        let mut seen = HashSet::new();
+        keys.retain(|key| seen.insert(*key));
+        Ok(keys)
+    }
+
+    /// Compute units a single swap on this venue should be budgeted.
+    ///
+    /// Titan uses this to size the compute-budget instruction per route.
+    /// Venues whose cost varies (e.g. CLMMs crossing many ticks) should return
+    /// a limit that covers their expensive cases.
+    fn compute_unit_hint(&self) -> u32 {
+        DEFAULT_COMPUTE_UNIT_HINT
+    }
+
     /// Construct the transaction instruction needed to execute a swap.
     ///
     /// This should use the amounts from the original `QuoteRequest`,
     /// not the `QuoteResult`. Venues should not modify swap semantics here;
     /// only build the appropriate on-chain instruction.
+    ///
+    /// The minimum output enforced on-chain should be derived from the quoted
+    /// output and `request.slippage_bps` (see `QuoteResult::minimum_received`).
     fn generate_swap_instruction(
         &self,
         request: QuoteRequest,
         user: Pubkey,
     ) -> Result<Instruction, TradingVenueError>;
 
+    /// Construct every instruction needed to execute a swap, in order.
+    ///
+    /// The default returns just `generate_swap_instruction`. Venues whose
+    /// swap needs setup first (ATA creation, an oracle crank) override this
+    /// and place the swap instruction last.
+    fn generate_swap_instructions(
+        &self,
+        request: QuoteRequest,
+        user: Pubkey,
+    ) -> Result<Vec<Instruction>, TradingVenueError> {
+        Ok(vec![self.generate_swap_instruction(request, user)?])
+    }
+
     /// Compute lower/upper admissible boundaries for valid input amounts
     /// using binary search over the venue's `quote()` function.
     ///
     /// This is used by Titan when determining safe routing ranges or when
     /// generating fallback limits.
     ///
-    /// `tkn_in_ind` and `tkn_out_ind` refer to token indices in `get_token_info()`.
+    /// `tkn_in_ind` and `tkn_out_ind` may be any two distinct token indices
+    /// in `get_token_info()`; see `all_pairs`.
     fn bounds(&self, tkn_in_ind: u8, tkn_out_ind: u8) -> Result<(u64, u64), TradingVenueError> {
-        let input_mint = self.get_token(tkn_in_ind as usize)?.pubkey;
-        let output_mint = self.get_token(tkn_out_ind as usize)?.pubkey;
+        let (input_mint, output_mint) = pair_mints(self, tkn_in_ind, tkn_out_ind)?;
 
         // Closure for boundary-finding—performs `ExactIn` quotes at various x.
         let f = |x: u64| {
-            self.quote(QuoteRequest {
-                amount: x,
-                swap_type: SwapType::ExactIn,
+            self.quote(QuoteRequest::new(
                 input_mint,
                 output_mint,
-            })
+                x,
+                SwapType::ExactIn,
+            ))
+        };
+
+        find_boundaries(&f)
+    }
+
+    /// Compute lower/upper admissible boundaries for valid *output* amounts
+    /// by probing `quote()` with `SwapType::ExactOut`.
+    ///
+    /// Only meaningful for venues that support `ExactOut`; venues that reject
+    /// it make the search fail with `NoQuotableValue` or
+    /// `BoundarySearchFailed`.
+    ///
+    /// `tkn_in_ind` and `tkn_out_ind` refer to token indices in `get_token_info()`.
+    fn bounds_exact_out(
+        &self,
+        tkn_in_ind: u8,
+        tkn_out_ind: u8,
+    ) -> Result<(u64, u64), TradingVenueError> {
+        let (input_mint, output_mint) = pair_mints(self, tkn_in_ind, tkn_out_ind)?;
+
+        let f = |x: u64| {
+            self.quote(QuoteRequest::new(
+                input_mint,
+                output_mint,
+                x,
+                SwapType::ExactOut,
+            ))
         };
 
         find_boundaries(&f)
     }
+
+    /// Same as `bounds`, but also returns the `ExactIn` quotes at both bounds
+    /// as `(lower_bound, lower_quote, upper_bound, upper_quote)`.
+    ///
+    /// Prefer this over `bounds` followed by `quote()` at each bound; the
+    /// quotes are reused from the boundary search.
+    fn bounds_with_quotes(
+        &self,
+        tkn_in_ind: u8,
+        tkn_out_ind: u8,
+    ) -> Result<(u64, QuoteResult, u64, QuoteResult), TradingVenueError> {
+        let (input_mint, output_mint) = pair_mints(self, tkn_in_ind, tkn_out_ind)?;
+
+        let f = |x: u64| {
+            self.quote(QuoteRequest::new(
+                input_mint,
+                output_mint,
+                x,
+                SwapType::ExactIn,
+            ))
+        };
+
+        find_boundaries_with_quotes(&f)
+    }
+
+    /// Largest `ExactIn` amount of `input_mint` the venue can fill in full
+    /// for `output_mint`; one atom more is a partial fill or fails.
+    ///
+    /// The default is the upper bound of `bounds`. Venues whose capacity
+    /// follows in closed form from their reserves should override it to
+    /// skip the search.
+    ///
+    /// # Errors
+    /// - `InvalidMint` if the venue does not trade either mint, or they are equal
+    /// - Any error from `bounds`
+    fn max_safe_input(
+        &self,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+    ) -> Result<u64, TradingVenueError> {
+        let tkn_in_ind = token_index(self, &input_mint)?;
+        let tkn_out_ind = token_index(self, &output_mint)?;
+        let (_, upper) = self.bounds(tkn_in_ind, tkn_out_ind)?;
+        Ok(upper)
+    }
+}
+
+/// Index of `mint` in `venue.get_token_info()`.
+///
+/// # Errors
+/// - `InvalidMint` if the venue does not trade `mint`
+/// - `TokenInfoIndexError` if the index does not fit in a `u8`
+fn token_index<V: TradingVenue + ?Sized>(
+    venue: &V,
+    mint: &Pubkey,
+) -> Result<u8, TradingVenueError> {
+    let index = venue
+        .get_token_info()
+        .iter()
+        .position(|token| token.pubkey == *mint)
+        .ok_or(TradingVenueError::InvalidMint(mint.into()))?;
+
+    u8::try_from(index).map_err(|_| TradingVenueError::TokenInfoIndexError(index))
+}
+
+/// Mints at `tkn_in_ind` and `tkn_out_ind`, which must differ.
+///
+/// # Errors
+/// - `TokenInfoIndexError` if either index is out of range
+/// - `InvalidMint` if both indices name the same token
+fn pair_mints<V: TradingVenue + ?Sized>(
+    venue: &V,
+    tkn_in_ind: u8,
+    tkn_out_ind: u8,
+) -> Result<(Pubkey, Pubkey), TradingVenueError> {
+    let input_mint = venue.get_token(tkn_in_ind as usize)?.pubkey;
+    let output_mint = venue.get_token(tkn_out_ind as usize)?.pubkey;
+    if tkn_in_ind == tkn_out_ind {
+        return Err(TradingVenueError::InvalidMint(input_mint.into()));
+    }
+    Ok((input_mint, output_mint))
 }
@@ -0,0 +1,135 @@
+//! Memoized quoting for venues whose `quote()` is expensive.
+//!
+//! A venue's quotes only change when its state does. `QuoteCache` remembers
+//! every successful quote and drops them all as soon as the wrapped venue's
+//! `last_updated_slot()` moves, so repeated identical requests between state
+//! updates are answered without re-running the venue's math. Within a slot
+//! the cache holds at most `max_entries` quotes, evicting the oldest first.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
+
+use ahash::AHashMap;
+use solana_pubkey::Pubkey;
+
+use crate::trading_venue::{
+    QuoteRequest, QuoteResult, SwapType, TradingVenue, error::TradingVenueError,
+};
+
+/// Default bound on the number of quotes cached within one slot.
+pub const DEFAULT_MAX_QUOTE_ENTRIES: usize = 4_096;
+
+/// Cache key: everything in a `QuoteRequest` that can change its result.
+type QuoteKey = (Pubkey, Pubkey, u64, SwapType);
+
+/// A `TradingVenue` paired with a cache of its quotes, keyed by
+/// `(input_mint, output_mint, amount, swap_type)` and valid for one
+/// `last_updated_slot()`.
+///
+/// Errors are never cached. Venues that do not report a slot (`None`) are
+/// only invalidated by `clear()`, so the capacity bound is what keeps their
+/// cache from growing without limit.
+pub struct QuoteCache {
+    venue: Box<dyn TradingVenue>,
+    entries: RefCell<AHashMap<QuoteKey, QuoteResult>>,
+    /// Cached keys in insertion order, oldest first.
+    order: RefCell<VecDeque<QuoteKey>>,
+    max_entries: usize,
+    slot: Cell<Option<u64>>,
+    hits: Cell<u64>,
+}
+
+impl QuoteCache {
+    /// Wrap `venue` with an empty cache of `DEFAULT_MAX_QUOTE_ENTRIES` quotes.
+    pub fn new(venue: Box<dyn TradingVenue>) -> Self {
+        let slot = Cell::new(venue.last_updated_slot());
+        Self {
+            venue,
+            entries: RefCell::default(),
+            order: RefCell::default(),
+            max_entries: DEFAULT_MAX_QUOTE_ENTRIES,
+            slot,
+            hits: Cell::new(0),
+        }
+    }
+
+    /// Bound the cache to at most `max_entries` quotes.
+    ///
+    /// Caching a new quote at the bound evicts the oldest cached one. A bound
+    /// of `0` disables caching.
+    pub fn with_capacity(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// The wrapped venue.
+    pub fn venue(&self) -> &dyn TradingVenue {
+        self.venue.as_ref()
+    }
+
+    /// Mutable access to the wrapped venue, e.g. for `update_state()`.
+    ///
+    /// Cached quotes are dropped on the next `quote()` if the update moved
+    /// `last_updated_slot()`.
+    pub fn venue_mut(&mut self) -> &mut dyn TradingVenue {
+        self.venue.as_mut()
+    }
+
+    /// Quote `request`, reusing a cached result from the current slot.
+    pub fn quote(&self, request: QuoteRequest) -> Result<QuoteResult, TradingVenueError> {
+        let slot = self.venue.last_updated_slot();
+        if slot != self.slot.get() {
+            self.clear();
+            self.slot.set(slot);
+        }
+
+        let key = (
+            request.input_mint,
+            request.output_mint,
+            request.amount,
+            request.swap_type,
+        );
+        if let Some(result) = self.entries.borrow().get(&key) {
+            self.hits.set(self.hits.get() + 1);
+            return Ok(result.clone());
+        }
+
+        let result = self.venue.quote(request)?;
+        if self.max_entries > 0 {
+            let mut entries = self.entries.borrow_mut();
+            let mut order = self.order.borrow_mut();
+            while entries.len() >= self.max_entries {
+                let Some(oldest) = order.pop_front() else {
+                    break;
+                };
+                entries.remove(&oldest);
+            }
+            entries.insert(key, result.clone());
+            order.push_back(key);
+        }
+        Ok(result)
+    }
+
+    /// Drop every cached quote.
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+        self.order.borrow_mut().clear();
+    }
+
+    /// Number of cached quotes.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    /// Whether no quotes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    /// Number of `quote()` calls answered from the cache so far.
+    pub fn hits(&self) -> u64 {
+        self.hits.get()
+    }
+}
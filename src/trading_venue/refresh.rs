@@ -0,0 +1,40 @@
+//! Batched state refresh across many venues.
+//!
+//! Updating venues one at a time costs a fetch round trip per venue.
+//! `refresh_all` first requests the union of every venue's required accounts
+//! in one `get_accounts` call, so caching implementations such as
+//! `RpcClientCache` can serve each subsequent `update_state()` from memory.
+
+use crate::{
+    account_caching::AccountsCache,
+    trading_venue::{TradingVenue, error::TradingVenueError},
+};
+
+/// Warm `cache` with every account `venues` need, then update each venue.
+///
+/// Returns one result per venue, in order. A failed warm-up is logged and
+/// otherwise ignored: each venue still fetches what it needs on update.
+pub async fn refresh_all(
+    venues: &mut [Box<dyn TradingVenue>],
+    cache: &dyn AccountsCache,
+) -> Vec<Result<(), TradingVenueError>> {
+    let mut pubkeys = Vec::new();
+    for venue in venues.iter() {
+        match venue.get_required_pubkeys_for_update() {
+            Ok(keys) => pubkeys.extend(keys),
+            Err(e) => log::warn!("Skipping warm-up for venue {}: {e}", venue.market_id()),
+        }
+    }
+    pubkeys.sort_unstable();
+    pubkeys.dedup();
+
+    if let Err(e) = cache.get_accounts(&pubkeys).await {
+        log::warn!("Failed to warm {} accounts: {e}", pubkeys.len());
+    }
+
+    let mut results = Vec::with_capacity(venues.len());
+    for venue in venues.iter_mut() {
+        results.push(venue.update_state(cache).await);
+    }
+    results
+}
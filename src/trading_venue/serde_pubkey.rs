@@ -0,0 +1,17 @@
+//! `serde(with = ...)` adapter that encodes a `Pubkey` as a base58 string.
+//!
+//! Only compiled with the `serde` feature.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serializer, de::Error};
+use solana_pubkey::Pubkey;
+
+pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    Pubkey::from_str(&encoded).map_err(D::Error::custom)
+}
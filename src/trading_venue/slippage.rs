@@ -0,0 +1,33 @@
+//! Slippage helpers shared by Titan venues.
+//!
+//! Venues use these when building swap instructions so that the on-chain
+//! program enforces a minimum output (`ExactIn`) or a maximum input
+//! (`ExactOut`) derived from the off-chain quote.
+
+use crate::trading_venue::error::TradingVenueError;
+
+/// Basis-point denominator (100% = 10_000 bps).
+pub const TEN_THOUSAND: u64 = 10_000;
+
+/// Reduce `amount` by `slippage_bps`, returning the minimum acceptable output.
+///
+/// Slippage values at or above 10_000 bps saturate to zero. The result never
+/// exceeds `amount`, so this cannot overflow.
+pub fn min_amount_with_slippage(amount: u64, slippage_bps: u64) -> u64 {
+    let remaining_bps = TEN_THOUSAND.saturating_sub(slippage_bps) as u128;
+    (amount as u128 * remaining_bps / TEN_THOUSAND as u128) as u64
+}
+
+/// Increase `amount` by `slippage_bps`, returning the maximum acceptable input.
+///
+/// Returns `CheckedMathError` if the result does not fit in a `u64`.
+pub fn max_amount_with_slippage(amount: u64, slippage_bps: u64) -> Result<u64, TradingVenueError> {
+    let multiplier = slippage_bps as u128 + TEN_THOUSAND as u128;
+    let max_amount = amount as u128 * multiplier / TEN_THOUSAND as u128;
+
+    u64::try_from(max_amount).map_err(|_| {
+        TradingVenueError::CheckedMathError(
+            format!("{amount} plus {slippage_bps} bps slippage overflows u64").into(),
+        )
+    })
+}
@@ -5,6 +5,7 @@
 //! mint belongs to the Token-2022 program. Titan venues use this metadata to
 //! compute swap quotes correctly, construct ATAs, and apply fee-aware routing.
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_account::Account;
 use solana_pubkey::Pubkey;
 use spl_associated_token_account::get_associated_token_address_with_program_id;
@@ -33,9 +34,11 @@ pub const TOKEN_2022_PROGRAM_ID: Pubkey = spl_token_2022::ID;
 ///
 /// Titan venues expose an array/slice of `TokenInfo` to describe the tokens
 /// they support on a per-pool basis.
-#[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TokenInfo {
     /// Mint address of the SPL token.
+    #[cfg_attr(feature = "serde", serde(with = "crate::trading_venue::serde_pubkey"))]
     pub pubkey: Pubkey,
 
     /// Number of decimal places a UI token amount should be scaled by.
@@ -107,6 +110,54 @@ impl TokenInfo {
         }
     }
 
+    /// Transfer fee charged when `amount` atoms are sent, following the
+    /// Token-2022 rules: `ceil(amount * transfer_fee / 10_000)`, capped at
+    /// `maximum_fee`. Mints without a transfer fee charge nothing.
+    pub fn transfer_fee_for(&self, amount: u64) -> u64 {
+        let bps = match self.transfer_fee {
+            Some(bps) if bps > 0 => bps as u128,
+            _ => return 0,
+        };
+
+        let fee = (amount as u128 * bps).div_ceil(10_000);
+        let fee = u64::try_from(fee).unwrap_or(u64::MAX);
+        fee.min(self.maximum_fee.unwrap_or(u64::MAX))
+    }
+
+    /// Amount the recipient actually receives when `amount` atoms are sent,
+    /// i.e. `amount` minus the mint's transfer fee.
+    pub fn amount_after_transfer_fee(&self, amount: u64) -> u64 {
+        amount.saturating_sub(self.transfer_fee_for(amount))
+    }
+
+    /// Smallest amount that must be sent so the recipient receives at least
+    /// `net` atoms after the transfer fee; the inverse of
+    /// `amount_after_transfer_fee`.
+    ///
+    /// The fee is charged on the gross amount, so below the cap the gross is
+    /// `ceil(net * 10_000 / (10_000 - transfer_fee))`, while once the fee
+    /// reaches `maximum_fee` it is simply `net + maximum_fee`. The result is
+    /// the smaller of the two, saturating at `u64::MAX`.
+    pub fn amount_before_transfer_fee(&self, net: u64) -> u64 {
+        let bps = match self.transfer_fee {
+            Some(bps) if bps > 0 => bps as u128,
+            _ => return net,
+        };
+        if net == 0 {
+            return 0;
+        }
+
+        let capped = net.saturating_add(self.maximum_fee.unwrap_or(u64::MAX));
+        if bps >= 10_000 {
+            return capped;
+        }
+
+        let uncapped = (net as u128 * 10_000).div_ceil(10_000 - bps);
+        let uncapped = u64::try_from(uncapped).unwrap_or(u64::MAX);
+
+        uncapped.min(capped)
+    }
+
     /// Return the SPL token program ID appropriate for this mint.
     ///
     /// - If `is_token_2022 == true`, returns `TOKEN_2022_PROGRAM_ID`
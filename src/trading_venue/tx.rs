@@ -0,0 +1,149 @@
+//! Transaction assembly helpers for Titan venues.
+//!
+//! Integrators repeatedly wrap `generate_swap_instructions` in a transaction
+//! to simulate or submit it. These helpers standardize that step, including
+//! compute-budget handling, and return unsigned v0 transactions for the
+//! caller to sign.
+//!
+//! `build_swap_instructions` optionally wraps native SOL around the swap, for
+//! users holding lamports rather than a funded WSOL account.
+
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use solana_sdk::{
+    message::{AddressLookupTableAccount, VersionedMessage, v0},
+    signature::Signature,
+};
+use solana_system_interface::instruction as system_instruction;
+use solana_transaction::versioned::VersionedTransaction;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use crate::trading_venue::{
+    QuoteRequest, SwapType, TradingVenue, error::TradingVenueError,
+    slippage::max_amount_with_slippage,
+};
+
+/// Build an unsigned v0 transaction executing `request` on `venue` for `user`.
+///
+/// `user` pays fees and signs. The venue's setup instructions (such as ATA
+/// creation) run before the swap. When `compute_unit_limit` is set, a
+/// compute-budget `SetComputeUnitLimit` instruction is prepended to them.
+pub fn build_swap_transaction(
+    venue: &dyn TradingVenue,
+    request: QuoteRequest,
+    user: Pubkey,
+    recent_blockhash: Hash,
+    compute_unit_limit: Option<u32>,
+) -> Result<VersionedTransaction, TradingVenueError> {
+    let message = build_swap_message(
+        venue,
+        request,
+        user,
+        recent_blockhash,
+        compute_unit_limit,
+        &[],
+    )?;
+
+    Ok(unsigned_transaction(message))
+}
+
+/// Compile the swap for `request` into a v0 message that loads accounts from
+/// `lookup_tables` where possible.
+///
+/// Any swap account found in one of the tables is referenced by index rather
+/// than stored in the message's static keys; signers and invoked programs
+/// always stay static. Comparing `static_account_keys()` with and without
+/// the tables shows how much a venue's lookup table keys actually save.
+pub fn build_swap_message(
+    venue: &dyn TradingVenue,
+    request: QuoteRequest,
+    user: Pubkey,
+    recent_blockhash: Hash,
+    compute_unit_limit: Option<u32>,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<VersionedMessage, TradingVenueError> {
+    let mut instructions = Vec::new();
+    if let Some(units) = compute_unit_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    }
+    instructions.extend(venue.generate_swap_instructions(request, user)?);
+
+    let message = v0::Message::try_compile(&user, &instructions, lookup_tables, recent_blockhash)
+        .map_err(|e| {
+        TradingVenueError::AmmMethodError(format!("Failed to compile swap message: {e}").into())
+    })?;
+
+    Ok(VersionedMessage::V0(message))
+}
+
+/// Build the instructions executing `request` on `venue` for `user`.
+///
+/// With `wrap_sol` set and native SOL on either side of the swap, the swap is
+/// surrounded by instructions moving lamports through the user's WSOL ATA:
+///
+/// - the ATA is created idempotently first
+/// - for SOL input, the swap input is transferred in and `sync_native`ed
+/// - the ATA is closed last, returning all remaining lamports to `user`
+///
+/// Otherwise the venue's `generate_swap_instructions` are returned as is.
+pub fn build_swap_instructions(
+    venue: &dyn TradingVenue,
+    request: QuoteRequest,
+    user: Pubkey,
+    wrap_sol: bool,
+) -> Result<Vec<Instruction>, TradingVenueError> {
+    let native_mint = spl_token::native_mint::ID;
+    let sol_in = request.input_mint == native_mint;
+    let sol_out = request.output_mint == native_mint;
+
+    if !wrap_sol || !(sol_in || sol_out) {
+        return venue.generate_swap_instructions(request, user);
+    }
+
+    let wsol_ata = get_associated_token_address(&user, &native_mint);
+    let create_wsol_ata =
+        create_associated_token_account_idempotent(&user, &user, &native_mint, &spl_token::ID);
+    let mut instructions = vec![create_wsol_ata.clone()];
+
+    if sol_in {
+        let lamports = match request.swap_type {
+            SwapType::ExactIn => request.amount,
+            SwapType::ExactOut => max_amount_with_slippage(
+                venue.quote(request.clone())?.amount,
+                request.slippage_bps,
+            )?,
+        };
+        instructions.push(system_instruction::transfer(&user, &wsol_ata, lamports));
+        instructions.push(
+            spl_token::instruction::sync_native(&spl_token::ID, &wsol_ata)
+                .map_err(|e| TradingVenueError::AmmMethodError(e.to_string().into()))?,
+        );
+    }
+
+    // The venue may create the WSOL ATA itself; it already exists by now.
+    instructions.extend(
+        venue
+            .generate_swap_instructions(request, user)?
+            .into_iter()
+            .filter(|ix| *ix != create_wsol_ata),
+    );
+    instructions.push(
+        spl_token::instruction::close_account(&spl_token::ID, &wsol_ata, &user, &user, &[])
+            .map_err(|e| TradingVenueError::AmmMethodError(e.to_string().into()))?,
+    );
+
+    Ok(instructions)
+}
+
+/// Wrap `message` with default signatures for each required signer.
+fn unsigned_transaction(message: VersionedMessage) -> VersionedTransaction {
+    let signers = message.header().num_required_signatures as usize;
+    VersionedTransaction {
+        signatures: vec![Signature::default(); signers],
+        message,
+    }
+}
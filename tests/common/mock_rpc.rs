@@ -0,0 +1,176 @@
+//! A scripted `RpcSender` for exercising `RpcClientCache` without a network.
+//!
+//! The sender serves `getAccountInfo` / `getMultipleAccounts` from an
+//! in-memory account map, records every call it receives, and can be told to
+//! fail a number of upcoming calls or truncate their responses.
+
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use solana_account::Account;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    nonblocking::rpc_client::RpcClient,
+    rpc_request::RpcRequest,
+};
+use solana_pubkey::Pubkey;
+use solana_rpc_client::{
+    rpc_client::RpcClientConfig,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+
+/// Shared, inspectable state behind a `MockRpcSender`.
+#[derive(Default)]
+pub struct MockRpcState {
+    pub accounts: HashMap<Pubkey, Account>,
+    /// `(method, number of pubkeys)` for every call received.
+    pub calls: Vec<(RpcRequest, usize)>,
+    /// Number of upcoming calls that fail with a transient I/O error.
+    pub transient_failures: usize,
+    /// Number of upcoming `getMultipleAccounts` calls answered with their
+    /// last account dropped.
+    pub short_responses: usize,
+    /// Context slot reported with every response.
+    pub slot: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct MockRpc {
+    pub state: Arc<Mutex<MockRpcState>>,
+}
+
+impl MockRpc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, pubkey: Pubkey, account: Account) {
+        self.state.lock().unwrap().accounts.insert(pubkey, account);
+    }
+
+    pub fn set_slot(&self, slot: u64) {
+        self.state.lock().unwrap().slot = slot;
+    }
+
+    pub fn fail_next(&self, count: usize) {
+        self.state.lock().unwrap().transient_failures = count;
+    }
+
+    pub fn truncate_next(&self, count: usize) {
+        self.state.lock().unwrap().short_responses = count;
+    }
+
+    pub fn calls(&self) -> Vec<(RpcRequest, usize)> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    pub fn clear_calls(&self) {
+        self.state.lock().unwrap().calls.clear();
+    }
+
+    /// Build an `RpcClient` routed through this mock.
+    pub fn client(&self) -> RpcClient {
+        RpcClient::new_sender(
+            MockRpcSender {
+                state: self.state.clone(),
+            },
+            RpcClientConfig::default(),
+        )
+    }
+}
+
+struct MockRpcSender {
+    state: Arc<Mutex<MockRpcState>>,
+}
+
+#[async_trait]
+impl RpcSender for MockRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let mut state = self.state.lock().unwrap();
+
+        let keys: Vec<Pubkey> = match &params[0] {
+            Value::String(key) => vec![Pubkey::from_str(key).unwrap()],
+            Value::Array(keys) => keys
+                .iter()
+                .map(|key| Pubkey::from_str(key.as_str().unwrap()).unwrap())
+                .collect(),
+            _ => vec![],
+        };
+        state.calls.push((request, keys.len()));
+
+        if state.transient_failures > 0 {
+            state.transient_failures -= 1;
+            return Err(ClientError::from(ClientErrorKind::Io(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "mock timeout",
+            ))));
+        }
+
+        let value = match request {
+            RpcRequest::GetAccountInfo => ui_account(state.accounts.get(&keys[0])),
+            RpcRequest::GetMultipleAccounts => {
+                let mut accounts: Vec<Value> = keys
+                    .iter()
+                    .map(|key| ui_account(state.accounts.get(key)))
+                    .collect();
+                if state.short_responses > 0 {
+                    state.short_responses -= 1;
+                    accounts.pop();
+                }
+                Value::Array(accounts)
+            }
+            other => panic!("unexpected mock RPC request {other}"),
+        };
+
+        Ok(json!({ "context": { "slot": state.slot }, "value": value }))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        "mock".to_string()
+    }
+}
+
+fn ui_account(account: Option<&Account>) -> Value {
+    match account {
+        None => Value::Null,
+        Some(account) => json!({
+            "lamports": account.lamports,
+            "data": [base64(&account.data), "base64"],
+            "owner": account.owner.to_string(),
+            "executable": account.executable,
+            "rentEpoch": account.rent_epoch,
+            "space": account.data.len(),
+        }),
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,242 @@
+//! Offline fixtures shared by the integration tests.
+//!
+//! These build synthetic Oxedium accounts (vaults, mints, oracles) so venue
+//! state updates and quoting can be exercised without an RPC endpoint.
+
+#![allow(dead_code)]
+
+pub mod mock_rpc;
+pub mod recording_cache;
+
+use std::collections::HashMap;
+
+use borsh::BorshSerialize;
+use solana_account::Account;
+use solana_program_pack::Pack;
+use solana_pubkey::Pubkey;
+use solana_sysvar::clock::{self, Clock};
+use spl_token::state::Mint;
+
+use titan_integration_template::{
+    account_caching::static_cache::StaticAccountsCache,
+    oxedium::{
+        amm::{MINT_ORACLES, OXEDIUM_AMM_PROGRAM_ID, OxediumAmmVenue, treasury_pda},
+        states::{PriceFeedMessage, PriceUpdateV2, Treasury, Vault, VerificationLevel},
+        utils::{
+            ANCHOR_DISCRIMINATOR_LEN, PRICE_UPDATE_V2_DISCRIMINATOR, TREASURY_DISCRIMINATOR,
+            VAULT_DISCRIMINATOR, VAULT_SEED,
+        },
+    },
+    trading_venue::{FromAccount, TradingVenue},
+};
+
+pub const SOL_MINT: Pubkey = MINT_ORACLES[0].0;
+pub const SOL_ORACLE: Pubkey = MINT_ORACLES[0].1;
+pub const USDC_MINT: Pubkey = MINT_ORACLES[1].0;
+pub const USDC_ORACLE: Pubkey = MINT_ORACLES[1].1;
+
+/// Publish time used for every synthetic oracle unless overridden.
+pub const PUBLISH_TIME: i64 = 1_700_000_000;
+
+pub fn vault_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[VAULT_SEED.as_bytes(), mint.as_ref()],
+        &OXEDIUM_AMM_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn vault(mint: Pubkey, oracle: Pubkey, liquidity: u64, base_fee: u64) -> Vault {
+    Vault {
+        is_active: true,
+        base_fee,
+        token_mint: mint,
+        pyth_price_account: oracle,
+        max_age_price: 60,
+        initial_liquidity: liquidity,
+        current_liquidity: liquidity,
+        max_liquidity: u64::MAX,
+        ..Vault::default()
+    }
+}
+
+pub fn price_update(price: i64, exponent: i32) -> PriceUpdateV2 {
+    PriceUpdateV2 {
+        write_authority: Pubkey::default(),
+        verification_level: VerificationLevel::Full,
+        price_message: PriceFeedMessage {
+            feed_id: [0; 32],
+            price,
+            conf: 0,
+            exponent,
+            prev_publish_time: PUBLISH_TIME,
+            publish_time: PUBLISH_TIME,
+        },
+        posted_slot: 1,
+    }
+}
+
+/// Serialize `value` behind its Anchor `discriminator`, zero-padded to `len` bytes.
+pub fn anchor_account(
+    discriminator: [u8; ANCHOR_DISCRIMINATOR_LEN],
+    value: &impl BorshSerialize,
+    len: usize,
+) -> Account {
+    let mut data = discriminator.to_vec();
+    value.serialize(&mut data).unwrap();
+    if data.len() < len {
+        data.resize(len, 0);
+    }
+
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner: OXEDIUM_AMM_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn mint_account(decimals: u8) -> Account {
+    let mut data = vec![0u8; Mint::LEN];
+    Mint::pack(
+        Mint {
+            decimals,
+            is_initialized: true,
+            supply: u64::MAX,
+            ..Mint::default()
+        },
+        &mut data,
+    )
+    .unwrap();
+
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn clock_account(slot: u64, unix_timestamp: i64) -> Account {
+    let clock = Clock {
+        slot,
+        unix_timestamp,
+        ..Clock::default()
+    };
+
+    Account {
+        lamports: 1_000_000,
+        data: bincode::serialize(&clock).unwrap(),
+        owner: solana_sdk_ids::sysvar::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// A SOL/USDC Oxedium market with balanced vaults, priced at 150 USDC per SOL.
+pub struct OxediumFixture {
+    pub market: Pubkey,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+    pub treasury: Treasury,
+    pub vaults: HashMap<Pubkey, Vault>,
+    pub mints: HashMap<Pubkey, u8>,
+    pub oracles: HashMap<Pubkey, PriceUpdateV2>,
+    pub extra: HashMap<Pubkey, Account>,
+}
+
+impl OxediumFixture {
+    pub fn new() -> Self {
+        let mut fixture = Self {
+            market: Pubkey::new_unique(),
+            slot: 1,
+            unix_timestamp: PUBLISH_TIME + 1,
+            treasury: Treasury::default(),
+            vaults: HashMap::new(),
+            mints: HashMap::new(),
+            oracles: HashMap::new(),
+            extra: HashMap::new(),
+        };
+
+        fixture.add_token(SOL_MINT, SOL_ORACLE, 9, 1_000_000_000_000, 30);
+        fixture.add_token(USDC_MINT, USDC_ORACLE, 6, 150_000_000_000, 30);
+        fixture
+            .oracles
+            .insert(SOL_ORACLE, price_update(15_000_000_000, -8));
+        fixture
+            .oracles
+            .insert(USDC_ORACLE, price_update(100_000_000, -8));
+
+        fixture
+    }
+
+    pub fn add_token(
+        &mut self,
+        mint: Pubkey,
+        oracle: Pubkey,
+        decimals: u8,
+        liquidity: u64,
+        base_fee: u64,
+    ) {
+        self.vaults
+            .insert(mint, vault(mint, oracle, liquidity, base_fee));
+        self.mints.insert(mint, decimals);
+    }
+
+    pub fn vault_mut(&mut self, mint: &Pubkey) -> &mut Vault {
+        self.vaults.get_mut(mint).unwrap()
+    }
+
+    pub fn oracle_mut(&mut self, oracle: &Pubkey) -> &mut PriceUpdateV2 {
+        self.oracles.get_mut(oracle).unwrap()
+    }
+
+    pub fn accounts(&self) -> HashMap<Pubkey, Account> {
+        let mut accounts = self.extra.clone();
+
+        accounts.insert(
+            treasury_pda(),
+            anchor_account(TREASURY_DISCRIMINATOR, &self.treasury, 0),
+        );
+        accounts.insert(clock::ID, clock_account(self.slot, self.unix_timestamp));
+
+        for (mint, vault) in &self.vaults {
+            accounts.insert(
+                vault_pda(mint),
+                anchor_account(
+                    VAULT_DISCRIMINATOR,
+                    vault,
+                    ANCHOR_DISCRIMINATOR_LEN + Vault::LEN,
+                ),
+            );
+        }
+        for (mint, decimals) in &self.mints {
+            accounts.insert(*mint, mint_account(*decimals));
+        }
+        for (oracle, update) in &self.oracles {
+            accounts.insert(
+                *oracle,
+                anchor_account(
+                    PRICE_UPDATE_V2_DISCRIMINATOR,
+                    update,
+                    ANCHOR_DISCRIMINATOR_LEN + PriceUpdateV2::LEN,
+                ),
+            );
+        }
+
+        accounts
+    }
+
+    pub fn cache(&self) -> StaticAccountsCache {
+        self.accounts().into_iter().collect()
+    }
+
+    /// Build a venue and run `update_state` against this fixture.
+    pub async fn venue(&self) -> OxediumAmmVenue {
+        let mut venue = OxediumAmmVenue::from_account(&self.market, &Account::default()).unwrap();
+        venue.update_state(&self.cache()).await.unwrap();
+        venue
+    }
+}
@@ -0,0 +1,72 @@
+//! An `AccountsCache` wrapper that records every pubkey requested through it.
+//!
+//! Used to assert that venues fetch exactly the accounts they declare in
+//! `get_required_pubkeys_for_update()`, without over- or under-fetching.
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+use titan_integration_template::account_caching::{AccountCacheError, AccountsCache};
+
+/// Delegates to `inner`, appending each requested pubkey to an ordered log.
+pub struct RecordingAccountsCache<C> {
+    inner: C,
+    requested: Mutex<Vec<Pubkey>>,
+}
+
+impl<C: AccountsCache> RecordingAccountsCache<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            requested: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Every pubkey requested so far, in request order, duplicates included.
+    pub fn requested_keys(&self) -> Vec<Pubkey> {
+        self.requested.lock().unwrap().clone()
+    }
+
+    pub fn clear(&self) {
+        self.requested.lock().unwrap().clear();
+    }
+
+    fn record(&self, pubkeys: &[Pubkey]) {
+        self.requested.lock().unwrap().extend_from_slice(pubkeys);
+    }
+}
+
+#[async_trait]
+impl<C: AccountsCache> AccountsCache for RecordingAccountsCache<C> {
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, AccountCacheError> {
+        self.record(std::slice::from_ref(pubkey));
+        self.inner.get_account(pubkey).await
+    }
+
+    async fn get_account_uncached(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<Option<Account>, AccountCacheError> {
+        self.record(std::slice::from_ref(pubkey));
+        self.inner.get_account_uncached(pubkey).await
+    }
+
+    async fn get_account_with_slot(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Result<(Option<Account>, u64), AccountCacheError> {
+        self.record(std::slice::from_ref(pubkey));
+        self.inner.get_account_with_slot(pubkey).await
+    }
+
+    async fn get_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Account>>, AccountCacheError> {
+        self.record(pubkeys);
+        self.inner.get_accounts(pubkeys).await
+    }
+}
@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod bounds {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::HashSet,
+    };
+
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::trading_venue::{
+        FeeBreakdown, QuoteResult,
+        bounds::{
+            BoundarySearchConfig, Tolerance, find_boundaries, find_boundaries_with_config,
+            find_boundaries_with_quotes_and_config, find_boundaries_with_tolerance,
+        },
+        error::TradingVenueError,
+    };
+
+    /// Synthetic venue whose quotes are valid exactly on `[lower, upper]`.
+    fn window(lower: u64, upper: u64) -> impl Fn(u64) -> Result<QuoteResult, TradingVenueError> {
+        move |amount| {
+            if amount > upper {
+                return Err(TradingVenueError::MathError("too large".into()));
+            }
+
+            Ok(QuoteResult {
+                input_mint: Pubkey::default(),
+                output_mint: Pubkey::default(),
+                amount,
+                expected_output: if amount >= lower { amount } else { 0 },
+                fee_amount: 0,
+                fees: FeeBreakdown::default(),
+                price_impact_bps: 0,
+                not_enough_liquidity: false,
+            })
+        }
+    }
+
+    #[test]
+    fn test_tighter_tolerance_narrows_interval() {
+        let (lower, upper) = (12_345, 987_654_321);
+        let f = window(lower, upper);
+
+        let (coarse_lower, coarse_upper) = find_boundaries(&f).unwrap();
+        let (tight_lower, tight_upper) = find_boundaries_with_tolerance(&f, 10).unwrap();
+
+        for (l, u) in [(coarse_lower, coarse_upper), (tight_lower, tight_upper)] {
+            assert!(l >= lower && u <= upper);
+        }
+        assert!(coarse_lower - lower <= 100 && upper - coarse_upper <= 100);
+        assert!(tight_lower - lower <= 10 && upper - tight_upper <= 10);
+        assert!(tight_lower <= coarse_lower && tight_upper >= coarse_upper);
+    }
+
+    #[test]
+    fn test_relative_tolerance_scales_with_bound() {
+        let (lower, upper) = (1_000, 5_000_000_000);
+        let f = window(lower, upper);
+
+        let (found_lower, found_upper) =
+            find_boundaries_with_tolerance(&f, Tolerance::Bps(100)).unwrap();
+
+        assert!(found_lower >= lower && found_lower - lower <= 100);
+        assert!(found_upper <= upper && upper - found_upper <= upper / 100);
+    }
+
+    #[test]
+    fn test_exact_refinement_on_odd_boundaries() {
+        // Odd boundaries used to stall `high / 2 + low / 2` once the bracket
+        // narrowed to two odd endpoints.
+        for (lower, upper) in [(1_001, 2_000_001), (3, 7), (999_999, 1_999_999)] {
+            let f = window(lower, upper);
+            assert_eq!(
+                find_boundaries_with_tolerance(&f, 0).unwrap(),
+                (lower, upper)
+            );
+        }
+    }
+
+    #[test]
+    fn test_repeated_probes_are_memoized() {
+        let probes = RefCell::new(Vec::new());
+        let inner = window(12_345, 987_654_321);
+        let f = |amount: u64| {
+            probes.borrow_mut().push(amount);
+            inner(amount)
+        };
+
+        let bounds = find_boundaries(&f).unwrap();
+        assert_eq!(
+            bounds,
+            find_boundaries(&window(12_345, 987_654_321)).unwrap()
+        );
+
+        let probes = probes.into_inner();
+        let unique: HashSet<u64> = probes.iter().copied().collect();
+        assert_eq!(probes.len(), unique.len());
+    }
+
+    #[test]
+    fn test_probe_budget_is_respected() {
+        // A pathological venue that never produces a valid quote forces the
+        // coarse search to walk all the way to `u64::MAX`.
+        let calls = Cell::new(0usize);
+        let f = |amount: u64| {
+            calls.set(calls.get() + 1);
+            window(u64::MAX, u64::MAX)(amount)
+        };
+
+        let config = BoundarySearchConfig {
+            max_probes: Some(8),
+            ..Default::default()
+        };
+        let result = find_boundaries_with_config(&f, config);
+
+        assert!(matches!(
+            result,
+            Err(TradingVenueError::BoundarySearchFailed(_))
+        ));
+        assert_eq!(calls.get(), 8);
+    }
+
+    #[test]
+    fn test_sufficient_budget_does_not_change_result() {
+        let f = window(12_345, 987_654_321);
+        let config = BoundarySearchConfig {
+            max_probes: Some(200),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            find_boundaries_with_config(&f, config).unwrap(),
+            find_boundaries(&f).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_interval_detects_gap() {
+        // Valid on [1_000, 1e9] except for a hole the coarse search steps over.
+        let inner = window(1_000, 1_000_000_000);
+        let f = |amount: u64| {
+            if (150_000_000..250_000_000).contains(&amount) {
+                return Err(TradingVenueError::MathError("gap".into()));
+            }
+            inner(amount)
+        };
+
+        // Without verification the hole goes unnoticed.
+        assert!(find_boundaries(&f).is_ok());
+
+        let config = BoundarySearchConfig {
+            verify_samples: 9,
+            ..Default::default()
+        };
+        assert!(matches!(
+            find_boundaries_with_config(&f, config),
+            Err(TradingVenueError::BoundarySearchFailed(_))
+        ));
+
+        // A contiguous region passes verification unchanged.
+        let contiguous = window(1_000, 1_000_000_000);
+        assert_eq!(
+            find_boundaries_with_config(&contiguous, config).unwrap(),
+            find_boundaries(&contiguous).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_quotes_search_honours_config() {
+        let f = window(12_345, 987_654_321);
+        let config = BoundarySearchConfig {
+            tolerance: Tolerance::Atoms(0),
+            verify_samples: 9,
+            ..Default::default()
+        };
+
+        let (lower, lower_quote, upper, upper_quote) =
+            find_boundaries_with_quotes_and_config(&f, config).unwrap();
+        assert_eq!((lower, upper), (12_345, 987_654_321));
+        assert_eq!(lower_quote, f(lower).unwrap());
+        assert_eq!(upper_quote, f(upper).unwrap());
+
+        let calls = Cell::new(0usize);
+        let counted = |amount: u64| {
+            calls.set(calls.get() + 1);
+            f(amount)
+        };
+        let budgeted = BoundarySearchConfig {
+            max_probes: Some(8),
+            ..config
+        };
+        assert!(matches!(
+            find_boundaries_with_quotes_and_config(&counted, budgeted),
+            Err(TradingVenueError::BoundarySearchFailed(_))
+        ));
+        assert_eq!(calls.get(), 8);
+    }
+
+    /// Synthetic constant-product pool accepting at most `max_in` input atoms,
+    /// quoted as `ExactIn` (`exact_out == false`) or `ExactOut`.
+    fn constant_product(
+        reserve_in: u64,
+        reserve_out: u64,
+        max_in: u64,
+        exact_out: bool,
+    ) -> impl Fn(u64) -> Result<QuoteResult, TradingVenueError> {
+        move |x| {
+            let (r_in, r_out) = (reserve_in as u128, reserve_out as u128);
+            let (amount, expected_output) = if exact_out {
+                if x as u128 >= r_out {
+                    return Err(TradingVenueError::MathError("drains pool".into()));
+                }
+                let required = (r_in * x as u128).div_ceil(r_out - x as u128);
+                (u64::try_from(required).unwrap_or(u64::MAX), x)
+            } else {
+                (x, (r_out * x as u128 / (r_in + x as u128)) as u64)
+            };
+
+            if amount > max_in {
+                return Err(TradingVenueError::MathError("too large".into()));
+            }
+
+            Ok(QuoteResult {
+                input_mint: Pubkey::default(),
+                output_mint: Pubkey::default(),
+                amount,
+                expected_output,
+                fee_amount: 0,
+                fees: FeeBreakdown::default(),
+                price_impact_bps: 0,
+                not_enough_liquidity: false,
+            })
+        }
+    }
+
+    #[test]
+    fn test_exact_out_upper_bound_input_within_exact_in_range() {
+        let (reserve_in, reserve_out, max_in) = (1_000_000_000, 2_000_000_000, 750_000_000_000);
+        let exact_in = constant_product(reserve_in, reserve_out, max_in, false);
+        let exact_out = constant_product(reserve_in, reserve_out, max_in, true);
+
+        let (in_lower, in_upper) = find_boundaries_with_tolerance(&exact_in, 1).unwrap();
+        let (out_lower, out_upper) = find_boundaries_with_tolerance(&exact_out, 1).unwrap();
+        assert!(out_lower <= out_upper && out_upper < reserve_out);
+
+        let required_input = exact_out(out_upper).unwrap().amount;
+        assert!(in_lower <= required_input && required_input <= in_upper);
+        assert!(exact_in(required_input).unwrap().expected_output >= out_upper);
+    }
+}
@@ -66,6 +66,7 @@ mod test_construction {
                     output_mint,
                     amount: lower_bound,
                     swap_type: SwapType::ExactIn,
+                    slippage_bps: 0,
                 })
             })
             .expect("Lower-bound quote failed");
@@ -79,6 +80,7 @@ mod test_construction {
                     output_mint,
                     amount: upper_bound,
                     swap_type: SwapType::ExactIn,
+                    slippage_bps: 0,
                 })
             })
             .expect("Upper-bound quote failed");
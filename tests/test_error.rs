@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod error {
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::account_caching::AccountCacheError;
+    use titan_integration_template::trading_venue::{
+        error::{ErrorCategory, ErrorInfo, TradingVenueError},
+        protocol::PoolProtocol,
+    };
+
+    #[test]
+    fn test_errors_compare_by_variant_and_payload() {
+        let mint = Pubkey::new_unique();
+
+        assert_eq!(
+            TradingVenueError::InvalidMint(mint.into()),
+            TradingVenueError::InvalidMint(ErrorInfo::Pubkey(mint))
+        );
+        assert_ne!(
+            TradingVenueError::InvalidMint(mint.into()),
+            TradingVenueError::InvalidMint(Pubkey::new_unique().into())
+        );
+        assert_ne!(
+            TradingVenueError::InvalidMint(mint.into()),
+            TradingVenueError::VaultNotFound(mint.into())
+        );
+
+        assert_eq!(
+            TradingVenueError::OracleNotFound,
+            TradingVenueError::OracleNotFound
+        );
+        assert_eq!(
+            TradingVenueError::InactivePoolError(mint, PoolProtocol::Oxedium),
+            TradingVenueError::InactivePoolError(mint, PoolProtocol::Oxedium)
+        );
+        assert_ne!(
+            TradingVenueError::TokenInfoIndexError(0),
+            TradingVenueError::TokenInfoIndexError(1)
+        );
+    }
+
+    #[test]
+    fn test_boxed_errors_compare_by_message() {
+        let boxed = |msg: &str| TradingVenueError::SomethingWentWrong(msg.to_string().into());
+
+        assert_eq!(boxed("oops"), boxed("oops"));
+        assert_ne!(boxed("oops"), boxed("other"));
+    }
+
+    #[test]
+    fn test_errors_are_categorized() {
+        let key = Pubkey::new_unique();
+
+        let cases = [
+            (
+                TradingVenueError::FailedToFetchAccountData(key.into()),
+                ErrorCategory::Infrastructure,
+            ),
+            (
+                TradingVenueError::CacheUnlockFailed,
+                ErrorCategory::Infrastructure,
+            ),
+            (
+                TradingVenueError::NotInitialized("venue not initialized".into()),
+                ErrorCategory::State,
+            ),
+            (
+                TradingVenueError::StaleOracle(key.into()),
+                ErrorCategory::State,
+            ),
+            (
+                TradingVenueError::InactivePoolError(key, PoolProtocol::Oxedium),
+                ErrorCategory::State,
+            ),
+            (
+                TradingVenueError::CheckedMathError(key.into()),
+                ErrorCategory::Math,
+            ),
+            (
+                TradingVenueError::InvalidMint(key.into()),
+                ErrorCategory::Unsupported,
+            ),
+            (
+                TradingVenueError::ExactOutNotSupported,
+                ErrorCategory::Unsupported,
+            ),
+            (
+                TradingVenueError::NoQuotableValue(key.into()),
+                ErrorCategory::Liquidity,
+            ),
+            (
+                TradingVenueError::NotEnoughLiquidity(key.into()),
+                ErrorCategory::Liquidity,
+            ),
+        ];
+
+        for (error, category) in cases {
+            assert_eq!(error.category(), category, "{error}");
+        }
+    }
+
+    #[test]
+    fn test_clones_equal_their_original() {
+        let mint = Pubkey::new_unique();
+        let errors = [
+            TradingVenueError::InvalidMint(mint.into()),
+            TradingVenueError::TokenInfoIndexError(3),
+            TradingVenueError::InactivePoolError(mint, PoolProtocol::Oxedium),
+            TradingVenueError::SomethingWentWrong("unexpected".into()),
+            TradingVenueError::from(AccountCacheError::ChannelError("closed".to_string())),
+        ];
+
+        for error in errors {
+            assert_eq!(error.clone(), error);
+            assert_eq!(error.clone().to_string(), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_channel_error_renders_its_message() {
+        let error = AccountCacheError::ChannelError("receiver dropped".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Account pipeline channel error: receiver dropped"
+        );
+
+        let venue_error = TradingVenueError::from(error);
+        assert_eq!(venue_error.category(), ErrorCategory::Infrastructure);
+    }
+}
@@ -0,0 +1,1740 @@
+mod common;
+
+#[cfg(test)]
+mod oxedium_venue {
+    use borsh::BorshDeserialize;
+    use rand::Rng;
+    use solana_account::Account;
+    use solana_pubkey::Pubkey;
+    use solana_sysvar::clock;
+    use spl_associated_token_account::{
+        get_associated_token_address, get_associated_token_address_with_program_id,
+    };
+
+    use titan_integration_template::account_caching::{
+        AccountsCache, static_cache::StaticAccountsCache,
+    };
+    use titan_integration_template::oxedium::amm::{
+        DEFAULT_MAX_CONF_BPS, DEFAULT_MAX_PRICE_AGE_SECS, OXEDIUM_AMM_PROGRAM_ID,
+        OXEDIUM_COMPUTE_UNIT_HINT, OxediumAmmVenue, UpdateWarning, treasury_pda,
+    };
+    use titan_integration_template::oxedium::components::compute_swap_math;
+    use titan_integration_template::oxedium::states::{SwapIxData, Vault, VerificationLevel};
+    use titan_integration_template::oxedium::utils::{
+        ANCHOR_DISCRIMINATOR_LEN, OxediumVenueError, PRICE_UPDATE_V2_DISCRIMINATOR,
+        TREASURY_DISCRIMINATOR, VAULT_DISCRIMINATOR,
+    };
+    use titan_integration_template::trading_venue::{
+        DEFAULT_COMPUTE_UNIT_HINT, FromAccount, QuoteRequest, SwapType, TradingVenue,
+        error::{ErrorCategory, TradingVenueError},
+    };
+
+    use crate::common::recording_cache::RecordingAccountsCache;
+    use crate::common::{
+        OxediumFixture, PUBLISH_TIME, SOL_MINT, SOL_ORACLE, USDC_MINT, USDC_ORACLE, mint_account,
+        price_update, vault, vault_pda,
+    };
+
+    const ONE_SOL: u64 = 1_000_000_000;
+
+    fn sol_to_usdc(amount: u64) -> QuoteRequest {
+        QuoteRequest::new(SOL_MINT, USDC_MINT, amount, SwapType::ExactIn)
+    }
+
+    #[tokio::test]
+    async fn test_slippage_lowers_min_amount_out() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+
+        let quote = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+
+        let mut prev = u64::MAX;
+        for slippage_bps in [0, 50, 100, 1_000] {
+            let mut request = sol_to_usdc(ONE_SOL);
+            request.slippage_bps = slippage_bps;
+
+            let ix = venue.generate_swap_instruction(request, user).unwrap();
+            let data = SwapIxData::try_from_slice(&ix.data[8..]).unwrap();
+
+            assert_eq!(data.amount_in, ONE_SOL);
+            if slippage_bps == 0 {
+                assert_eq!(data.min_amount_out, quote.expected_output);
+            }
+            assert!(data.min_amount_out < prev);
+            prev = data.min_amount_out;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zero_fee_pool_reports_no_fee() {
+        let mut fixture = OxediumFixture::new();
+        fixture.vault_mut(&SOL_MINT).base_fee = 0;
+        fixture.vault_mut(&USDC_MINT).base_fee = 0;
+        let venue = fixture.venue().await;
+
+        let quote = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        assert_eq!(quote.fee_amount, 0);
+        assert_eq!(quote.expected_output, 150_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_fee_amount_in_input_atoms() {
+        let venue = OxediumFixture::new().venue().await;
+
+        // 30 bps LP fee on 1 SOL.
+        let quote = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        assert_eq!(quote.fee_amount, 3_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_price_impact_grows_with_size() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let zero = venue.quote(sol_to_usdc(0)).unwrap();
+        assert_eq!(zero.price_impact_bps, 0);
+
+        let mut prev = 0;
+        for sol in [1, 10, 50, 200] {
+            let quote = venue.quote(sol_to_usdc(sol * ONE_SOL)).unwrap();
+            assert!(quote.price_impact_bps >= prev);
+            prev = quote.price_impact_bps;
+        }
+
+        // Trades above 10% of the output vault pay 10x the base fee.
+        assert_eq!(prev, 300);
+    }
+
+    #[tokio::test]
+    async fn test_stoptap_halts_quoting_and_swaps() {
+        let mut venue = OxediumFixture::new().venue().await;
+        assert!(venue.is_tradable());
+        venue.treasury.stoptap = true;
+
+        assert!(venue.initialized());
+        assert!(!venue.is_tradable());
+
+        assert!(matches!(
+            venue.quote(sol_to_usdc(ONE_SOL)),
+            Err(TradingVenueError::InactivePoolError(..))
+        ));
+        assert!(matches!(
+            venue.generate_swap_instruction(sol_to_usdc(ONE_SOL), Pubkey::new_unique()),
+            Err(TradingVenueError::InactivePoolError(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stoptap_off_quotes_normally() {
+        let mut venue = OxediumFixture::new().venue().await;
+        venue.treasury.stoptap = false;
+
+        let quote = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        assert!(quote.expected_output > 0);
+        assert!(
+            venue
+                .generate_swap_instruction(sol_to_usdc(ONE_SOL), Pubkey::new_unique())
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_treasury_fee_reduces_output() {
+        let mut fixture = OxediumFixture::new();
+        let free = fixture.venue().await;
+        assert_eq!(free.treasury.fee_bps, 0);
+
+        fixture.treasury.fee_bps = 20;
+        let charged = fixture.venue().await;
+        assert_eq!(charged.treasury.fee_bps, 20);
+
+        let free_quote = free.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        let charged_quote = charged.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        assert!(charged_quote.expected_output < free_quote.expected_output);
+    }
+
+    #[tokio::test]
+    async fn test_prices_scaled_by_exponent() {
+        let baseline = OxediumFixture::new().venue().await;
+        let expected = baseline
+            .quote(sol_to_usdc(ONE_SOL))
+            .unwrap()
+            .expected_output;
+
+        // Same prices expressed with different exponents on each side.
+        for (sol_price, sol_expo, usdc_price, usdc_expo) in [
+            (15_000_000, -5, 100_000_000, -8),
+            (15_000_000_000, -8, 1_000_000, -6),
+            (15, 1, 1, 0),
+        ] {
+            let mut fixture = OxediumFixture::new();
+            fixture
+                .oracles
+                .insert(SOL_ORACLE, price_update(sol_price, sol_expo));
+            fixture
+                .oracles
+                .insert(USDC_ORACLE, price_update(usdc_price, usdc_expo));
+            let venue = fixture.venue().await;
+
+            let quote = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+            assert_eq!(quote.expected_output, expected);
+
+            let reverse = venue
+                .quote(QuoteRequest::new(
+                    USDC_MINT,
+                    SOL_MINT,
+                    150_000_000,
+                    SwapType::ExactIn,
+                ))
+                .unwrap();
+            assert!(reverse.expected_output > 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_negative_price_is_rejected() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracle_mut(&SOL_ORACLE).price_message.price = -15_000_000_000;
+        let venue = fixture.venue().await;
+
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err(),
+            TradingVenueError::InvalidOraclePrice(SOL_ORACLE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_negative_output_price_is_rejected() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracle_mut(&USDC_ORACLE).price_message.price = -100_000_000;
+        let venue = fixture.venue().await;
+
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err(),
+            TradingVenueError::InvalidOraclePrice(USDC_ORACLE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_price_rescaled_to_zero_is_rejected() {
+        let mut fixture = OxediumFixture::new();
+        let message = &mut fixture.oracle_mut(&SOL_ORACLE).price_message;
+        message.price = 1;
+        message.conf = 0;
+        message.exponent = -30;
+        let venue = fixture.venue().await;
+
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err(),
+            TradingVenueError::InvalidOraclePrice(SOL_ORACLE.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_oracle_is_rejected() {
+        let mut fixture = OxediumFixture::new();
+        fixture.unix_timestamp = PUBLISH_TIME + DEFAULT_MAX_PRICE_AGE_SECS as i64 + 1;
+        let venue = fixture.venue().await;
+
+        assert!(matches!(
+            venue.quote(sol_to_usdc(ONE_SOL)),
+            Err(TradingVenueError::StaleOracle(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fresh_oracle_quotes() {
+        let mut fixture = OxediumFixture::new();
+        fixture.unix_timestamp = PUBLISH_TIME + DEFAULT_MAX_PRICE_AGE_SECS as i64;
+        let venue = fixture.venue().await;
+
+        assert_eq!(venue.clock_unix_timestamp, Some(fixture.unix_timestamp));
+        assert!(venue.quote(sol_to_usdc(ONE_SOL)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_quotes_rejected_without_clock() {
+        let fixture = OxediumFixture::new();
+        let mut accounts = fixture.accounts();
+        accounts.remove(&clock::ID);
+
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        venue.update_state_from_accounts(&accounts).unwrap();
+
+        assert_eq!(venue.clock_unix_timestamp, None);
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err(),
+            TradingVenueError::MissingState(clock::ID.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clock_missing_from_next_update_is_cleared() {
+        let fixture = OxediumFixture::new();
+        let mut venue = fixture.venue().await;
+        assert!(venue.quote(sol_to_usdc(ONE_SOL)).is_ok());
+
+        let mut accounts = fixture.accounts();
+        accounts.remove(&clock::ID);
+        venue.update_state_from_accounts(&accounts).unwrap();
+
+        assert_eq!(
+            venue.update_warnings(),
+            [UpdateWarning::MissingAccount(clock::ID)]
+        );
+        assert_eq!(venue.clock_unix_timestamp, None);
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err(),
+            TradingVenueError::MissingState(clock::ID.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_verification_rejected_when_full_required() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracle_mut(&USDC_ORACLE).verification_level =
+            VerificationLevel::Partial { num_signatures: 1 };
+        let mut venue = fixture.venue().await;
+
+        assert_eq!(venue.required_verification_level, VerificationLevel::Full);
+        assert!(matches!(
+            venue.quote(sol_to_usdc(ONE_SOL)),
+            Err(TradingVenueError::InsufficientOracleVerification(_))
+        ));
+
+        venue.required_verification_level = VerificationLevel::Partial { num_signatures: 1 };
+        assert!(venue.quote(sol_to_usdc(ONE_SOL)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_update_state_from_static_cache() {
+        let fixture = OxediumFixture::new();
+        let mut accounts = fixture.accounts().into_iter();
+
+        // Seed via `from_iter`, then top up via `insert`.
+        let (first_key, first_account) = accounts.next().unwrap();
+        let mut cache: StaticAccountsCache = accounts.collect();
+        assert!(cache.get_account(&first_key).await.unwrap().is_none());
+        cache.insert(first_key, first_account);
+
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        assert!(!venue.initialized());
+        venue.update_state(&cache).await.unwrap();
+
+        assert!(venue.initialized());
+        assert_eq!(venue.get_token_info().len(), 2);
+        assert_eq!(venue.vaults.len(), 2);
+        assert_eq!(venue.oracles.len(), 2);
+        assert!(venue.quote(sol_to_usdc(ONE_SOL)).unwrap().expected_output > 0);
+    }
+
+    #[tokio::test]
+    async fn test_quote_many_matches_individual_quotes() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let mut requests: Vec<QuoteRequest> = [0, 1, ONE_SOL, 100 * ONE_SOL, 10_000 * ONE_SOL]
+            .into_iter()
+            .map(sol_to_usdc)
+            .collect();
+        requests.push(QuoteRequest::new(
+            USDC_MINT,
+            SOL_MINT,
+            1_000_000,
+            SwapType::ExactIn,
+        ));
+
+        let batch = venue.quote_many(&requests);
+        assert_eq!(batch.len(), requests.len());
+
+        for (request, batched) in requests.into_iter().zip(batch) {
+            match (venue.quote(request), batched) {
+                (Ok(single), Ok(batched)) => assert_eq!(single, batched),
+                (Err(single), Err(batched)) => {
+                    assert_eq!(format!("{single:?}"), format!("{batched:?}"))
+                }
+                (single, batched) => panic!("mismatch: {single:?} vs {batched:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spot_price_matches_quote_slope_near_zero() {
+        let venue = OxediumFixture::new().venue().await;
+        let tokens = venue.get_token_info();
+        let sol = tokens.iter().position(|t| t.pubkey == SOL_MINT).unwrap() as u8;
+        let usdc = tokens.iter().position(|t| t.pubkey == USDC_MINT).unwrap() as u8;
+
+        let sol_in_usdc = venue.spot_price(sol, usdc).unwrap();
+        let usdc_in_sol = venue.spot_price(usdc, sol).unwrap();
+        assert!((sol_in_usdc - 150.0).abs() < 1e-9);
+        assert!((sol_in_usdc * usdc_in_sol - 1.0).abs() < 1e-9);
+
+        // The quote slope near zero differs from the mid price only by fees.
+        let probe = ONE_SOL / 1_000;
+        let quote = venue.quote(sol_to_usdc(probe)).unwrap();
+        let slope = (quote.expected_output as f64 / 1e6) / (probe as f64 / 1e9);
+        assert!(slope <= sol_in_usdc);
+        assert!((sol_in_usdc - slope) / sol_in_usdc < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_bounds_with_quotes_match_fresh_quotes() {
+        let venue = OxediumFixture::new().venue().await;
+
+        for (in_idx, out_idx) in [(0u8, 1u8), (1, 0)] {
+            let (lower, lower_quote, upper, upper_quote) =
+                venue.bounds_with_quotes(in_idx, out_idx).unwrap();
+            assert_eq!(venue.bounds(in_idx, out_idx).unwrap(), (lower, upper));
+
+            let input_mint = venue.get_token(in_idx as usize).unwrap().pubkey;
+            let output_mint = venue.get_token(out_idx as usize).unwrap().pubkey;
+            for (bound, quote) in [(lower, lower_quote), (upper, upper_quote)] {
+                let request = QuoteRequest::new(input_mint, output_mint, bound, SwapType::ExactIn);
+                assert_eq!(venue.quote(request).unwrap(), quote);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip_quotes_identically() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let snapshot = venue.to_snapshot().unwrap();
+        let restored = OxediumAmmVenue::from_snapshot(&snapshot).unwrap();
+
+        assert!(restored.initialized());
+        assert_eq!(restored.get_token_info(), venue.get_token_info());
+        assert_eq!(restored.to_snapshot().unwrap(), snapshot);
+
+        for (in_idx, out_idx) in [(0u8, 1u8), (1, 0)] {
+            let (lower, upper) = venue.bounds(in_idx, out_idx).unwrap();
+            assert_eq!(restored.bounds(in_idx, out_idx).unwrap(), (lower, upper));
+
+            let input_mint = venue.get_token(in_idx as usize).unwrap().pubkey;
+            let output_mint = venue.get_token(out_idx as usize).unwrap().pubkey;
+            let step = ((upper - lower) / 16).max(1);
+            for amount in (lower..=upper).step_by(step as usize) {
+                let request = QuoteRequest::new(input_mint, output_mint, amount, SwapType::ExactIn);
+                assert_eq!(
+                    restored.quote(request.clone()).unwrap(),
+                    venue.quote(request).unwrap()
+                );
+            }
+        }
+
+        assert!(OxediumAmmVenue::from_snapshot(&snapshot[..snapshot.len() / 2]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_swap_accounts_match_instruction() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+
+        for (input_mint, output_mint) in [(SOL_MINT, USDC_MINT), (USDC_MINT, SOL_MINT)] {
+            let accounts = venue
+                .get_swap_accounts(input_mint, output_mint, user)
+                .unwrap();
+            let request = QuoteRequest::new(input_mint, output_mint, 1_000_000, SwapType::ExactIn);
+            let ix = venue.generate_swap_instruction(request, user).unwrap();
+
+            assert_eq!(accounts, ix.accounts);
+        }
+    }
+
+    /// A venue whose `token_2022_mints` are owned by the Token-2022 program.
+    async fn venue_with_token_2022(
+        fixture: &OxediumFixture,
+        token_2022_mints: &[Pubkey],
+    ) -> OxediumAmmVenue {
+        let mut cache = fixture.cache();
+        for mint in token_2022_mints {
+            let mut account = cache.get_account(mint).await.unwrap().unwrap();
+            account.owner = spl_token_2022::ID;
+            cache.insert(*mint, account);
+        }
+
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        venue.update_state(&cache).await.unwrap();
+        venue
+    }
+
+    #[tokio::test]
+    async fn test_swap_accounts_use_token_2022_program() {
+        let fixture = OxediumFixture::new();
+        let venue = venue_with_token_2022(&fixture, &[SOL_MINT, USDC_MINT]).await;
+        let user = Pubkey::new_unique();
+        let treasury = treasury_pda();
+
+        let accounts = venue.get_swap_accounts(SOL_MINT, USDC_MINT, user).unwrap();
+        let ata = |owner: &Pubkey, mint: &Pubkey| {
+            get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::ID)
+        };
+
+        assert_eq!(accounts[5].pubkey, ata(&user, &SOL_MINT));
+        assert_eq!(accounts[6].pubkey, ata(&user, &USDC_MINT));
+        assert_eq!(accounts[10].pubkey, ata(&treasury, &SOL_MINT));
+        assert_eq!(accounts[11].pubkey, ata(&treasury, &USDC_MINT));
+        assert_eq!(accounts[13].pubkey, spl_token_2022::ID);
+        assert!(!accounts.iter().any(|meta| meta.pubkey == spl_token::ID));
+    }
+
+    #[tokio::test]
+    async fn test_swap_accounts_reject_mixed_token_programs() {
+        let fixture = OxediumFixture::new();
+        let venue = venue_with_token_2022(&fixture, &[SOL_MINT]).await;
+
+        assert!(matches!(
+            venue.get_swap_accounts(SOL_MINT, USDC_MINT, Pubkey::new_unique()),
+            Err(TradingVenueError::UnsupportedVenue(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_token_2022_mint_sets_token_program() {
+        let fixture = OxediumFixture::new();
+        let mut cache = fixture.cache();
+
+        let mut sol_mint = mint_account(9);
+        sol_mint.owner = spl_token_2022::ID;
+        cache.insert(SOL_MINT, sol_mint);
+
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        venue.update_state(&cache).await.unwrap();
+
+        let token = |mint| {
+            *venue
+                .get_token_info()
+                .iter()
+                .find(|t| t.pubkey == mint)
+                .unwrap()
+        };
+        let sol = token(SOL_MINT);
+        let usdc = token(USDC_MINT);
+
+        assert!(sol.is_token_2022);
+        assert_eq!(sol.decimals, 9);
+        assert_eq!(sol.get_token_program(), spl_token_2022::ID);
+        assert!(!usdc.is_token_2022);
+        assert_eq!(usdc.get_token_program(), spl_token::ID);
+    }
+
+    #[tokio::test]
+    async fn test_custom_mint_oracle_registry() {
+        let bonk_mint = Pubkey::new_unique();
+        let bonk_oracle = Pubkey::new_unique();
+
+        let mut fixture = OxediumFixture::new();
+        fixture.add_token(bonk_mint, bonk_oracle, 5, 1_000_000_000_000, 30);
+        fixture.oracles.insert(bonk_oracle, price_update(2_000, -8));
+
+        let registry = vec![
+            (SOL_MINT, SOL_ORACLE),
+            (USDC_MINT, USDC_ORACLE),
+            (bonk_mint, bonk_oracle),
+        ];
+        let mut venue = OxediumAmmVenue::new(fixture.market, registry);
+
+        let required = venue.get_required_pubkeys_for_update().unwrap();
+        assert!(required.contains(&bonk_mint));
+        assert!(required.contains(&bonk_oracle));
+        assert_eq!(venue.oracle_for_mint(&bonk_mint), Some(bonk_oracle));
+
+        venue.update_state(&fixture.cache()).await.unwrap();
+        assert_eq!(
+            venue.tradable_mints().unwrap(),
+            vec![SOL_MINT, USDC_MINT, bonk_mint]
+        );
+
+        let request = QuoteRequest::new(bonk_mint, USDC_MINT, 100_000_000, SwapType::ExactIn);
+        assert!(venue.quote(request.clone()).unwrap().expected_output > 0);
+        assert!(
+            venue
+                .generate_swap_instruction(request, Pubkey::new_unique())
+                .is_ok()
+        );
+
+        // The default registry knows nothing about the new listing.
+        let default_venue = fixture.venue().await;
+        assert_eq!(default_venue.get_token_info().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mint_with_missing_oracle_is_not_tradable() {
+        let bonk_mint = Pubkey::new_unique();
+        let bonk_oracle = Pubkey::new_unique();
+
+        // The BONK oracle account is never created.
+        let mut fixture = OxediumFixture::new();
+        fixture.add_token(bonk_mint, bonk_oracle, 5, 1_000_000_000_000, 30);
+
+        let registry = vec![
+            (bonk_mint, bonk_oracle),
+            (SOL_MINT, SOL_ORACLE),
+            (USDC_MINT, USDC_ORACLE),
+        ];
+        let mut venue = OxediumAmmVenue::new(fixture.market, registry);
+        venue.update_state(&fixture.cache()).await.unwrap();
+
+        assert_eq!(venue.tradable_mints().unwrap(), vec![SOL_MINT, USDC_MINT]);
+        assert_eq!(venue.decimals().unwrap(), vec![9, 6]);
+        assert_eq!(venue.get_token(0).unwrap().pubkey, SOL_MINT);
+
+        assert!(venue.quote(sol_to_usdc(ONE_SOL)).unwrap().expected_output > 0);
+        assert!(matches!(
+            venue.quote(QuoteRequest::new(
+                bonk_mint,
+                USDC_MINT,
+                1_000,
+                SwapType::ExactIn
+            )),
+            Err(TradingVenueError::InvalidMint(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reserves_report_vault_liquidity() {
+        let mut fixture = OxediumFixture::new();
+        fixture.vault_mut(&USDC_MINT).current_liquidity = 120_000_000_000;
+        let venue = fixture.venue().await;
+
+        assert_eq!(
+            venue.reserves().unwrap(),
+            vec![(SOL_MINT, 1_000_000_000_000), (USDC_MINT, 120_000_000_000)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_last_updated_slot_tracks_clock() {
+        let fixture = OxediumFixture::new();
+
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        assert_eq!(venue.last_updated_slot(), None);
+
+        venue.update_state(&fixture.cache()).await.unwrap();
+        assert_eq!(venue.last_updated_slot(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_fee_breakdown_sums_to_fee_amount() {
+        let mut fixture = OxediumFixture::new();
+        fixture.treasury.fee_bps = 20;
+        let venue = fixture.venue().await;
+
+        // 30 bps LP fee and 20 bps treasury fee on 1 SOL.
+        let quote = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        assert_eq!(quote.fees.lp_fee, 3_000_000);
+        assert_eq!(quote.fees.protocol_fee, 2_000_000);
+        assert_eq!(quote.fees.transfer_fee, 0);
+        assert_eq!(quote.fees.total(), quote.fee_amount);
+
+        for amount in [1, 7_777, ONE_SOL / 3, 50 * ONE_SOL] {
+            let quote = venue.quote(sol_to_usdc(amount)).unwrap();
+            assert_eq!(quote.fees.total(), quote.fee_amount);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_fees_reduce_quote() {
+        let fixture = OxediumFixture::new();
+        let fee_free = fixture.venue().await;
+        let mut fee_bearing = fixture.venue().await;
+        for token in &mut fee_bearing.token_infos {
+            // 1% on SOL going in, 0.5% on USDC coming out.
+            token.transfer_fee = Some(if token.pubkey == SOL_MINT { 100 } else { 50 });
+            token.maximum_fee = Some(u64::MAX);
+        }
+
+        let quote = fee_bearing.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        let fee_free_quote = fee_free.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        assert_eq!(fee_free_quote.fees.transfer_fee, 0);
+        assert!(quote.expected_output < fee_free_quote.expected_output);
+
+        // The vault only sees 0.99 SOL, and 0.5% of the output is withheld.
+        let net_in_quote = fee_free
+            .quote(sol_to_usdc(ONE_SOL - ONE_SOL / 100))
+            .unwrap();
+        let usdc = fee_bearing.get_token_by_mint(&USDC_MINT).unwrap();
+        assert_eq!(
+            quote.expected_output,
+            usdc.amount_after_transfer_fee(net_in_quote.expected_output)
+        );
+        assert_eq!(quote.fees.lp_fee, net_in_quote.fees.lp_fee);
+        assert!(quote.fees.transfer_fee > ONE_SOL / 100);
+        assert_eq!(quote.fees.total(), quote.fee_amount);
+    }
+
+    #[tokio::test]
+    async fn test_vault_layout_mismatch_is_rejected() {
+        let corruptions: [fn(&mut Vault); 2] = [
+            |vault| vault.token_mint = Pubkey::new_unique(),
+            |vault| vault.pyth_price_account = Pubkey::new_unique(),
+        ];
+
+        for corrupt in corruptions {
+            let mut fixture = OxediumFixture::new();
+            corrupt(fixture.vault_mut(&USDC_MINT));
+            let usdc_vault = vault_pda(&USDC_MINT);
+
+            // Lenient: the vault is skipped and its mint is not tradable.
+            let venue = fixture.venue().await;
+            assert_eq!(
+                venue.update_warnings(),
+                [UpdateWarning::UndecodableAccount(usdc_vault)]
+            );
+            assert!(!venue.vaults.contains_key(&USDC_MINT));
+            assert_eq!(venue.tradable_mints().unwrap(), vec![SOL_MINT]);
+
+            // Strict: the update is rejected.
+            let mut venue =
+                OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+            venue.strict = true;
+            assert_eq!(
+                venue.update_state(&fixture.cache()).await.unwrap_err(),
+                TradingVenueError::MissingState(usdc_vault.into())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_table_keys_cover_shared_swap_accounts() {
+        let venue = OxediumFixture::new().venue().await;
+        let keys = venue.get_lookup_table_keys(None).await.unwrap();
+
+        let mut unique = keys.clone();
+        unique.sort();
+        unique.dedup();
+        assert!(!keys.is_empty());
+        assert_eq!(unique.len(), keys.len());
+
+        for key in [
+            vault_pda(&SOL_MINT),
+            vault_pda(&USDC_MINT),
+            SOL_ORACLE,
+            USDC_ORACLE,
+            treasury_pda(),
+        ] {
+            assert!(keys.contains(&key), "missing {key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_table_keys_cover_swap_instruction() {
+        let venue = OxediumFixture::new().venue().await;
+        let keys = venue.get_lookup_table_keys(None).await.unwrap();
+        let user = Pubkey::new_unique();
+
+        for (input_mint, output_mint) in [(SOL_MINT, USDC_MINT), (USDC_MINT, SOL_MINT)] {
+            let request = QuoteRequest::new(input_mint, output_mint, ONE_SOL, SwapType::ExactIn);
+            let ix = venue.generate_swap_instruction(request, user).unwrap();
+            let user_owned = [
+                user,
+                get_associated_token_address(&user, &input_mint),
+                get_associated_token_address(&user, &output_mint),
+            ];
+
+            assert!(keys.contains(&ix.program_id));
+            for meta in ix.accounts {
+                if !user_owned.contains(&meta.pubkey) {
+                    assert!(keys.contains(&meta.pubkey), "missing {}", meta.pubkey);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quote_async_reads_fresh_oracle_prices() {
+        let mut fixture = OxediumFixture::new();
+        let venue = fixture.venue().await;
+        let stale = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+
+        fixture.oracle_mut(&SOL_ORACLE).price_message.price = 20_000_000_000;
+        let cache = fixture.cache();
+
+        let fresh = venue
+            .quote_async(&cache, sol_to_usdc(ONE_SOL))
+            .await
+            .unwrap();
+
+        assert_eq!(venue.quote(sol_to_usdc(ONE_SOL)).unwrap(), stale);
+        assert!(fresh.expected_output > stale.expected_output);
+        assert_eq!(
+            fresh,
+            fixture.venue().await.quote(sol_to_usdc(ONE_SOL)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zero_input_quotes_zero_output() {
+        let venue = OxediumFixture::new().venue().await;
+
+        for (input_mint, output_mint) in [(SOL_MINT, USDC_MINT), (USDC_MINT, SOL_MINT)] {
+            let request = QuoteRequest::new(input_mint, output_mint, 0, SwapType::ExactIn);
+            let quote = venue.quote(request).unwrap();
+
+            assert_eq!(quote.amount, 0);
+            assert_eq!(quote.expected_output, 0);
+            assert_eq!(quote.fee_amount, 0);
+            assert!(!quote.not_enough_liquidity);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_same_mint_swap_is_rejected() {
+        let venue = OxediumFixture::new().venue().await;
+        let request = QuoteRequest::new(SOL_MINT, SOL_MINT, ONE_SOL, SwapType::ExactIn);
+
+        assert_eq!(
+            venue.quote(request).unwrap_err(),
+            TradingVenueError::InvalidMint(SOL_MINT.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_fill_reports_max_consumable_input() {
+        let venue = OxediumFixture::new().venue().await;
+
+        for amount in [
+            ONE_SOL,
+            100 * ONE_SOL,
+            10_000 * ONE_SOL,
+            1_000_000 * ONE_SOL,
+        ] {
+            let quote = venue.quote(sol_to_usdc(amount)).unwrap();
+            assert_eq!(quote.not_enough_liquidity, quote.amount < amount);
+        }
+
+        let partial = venue.quote(sol_to_usdc(10_000 * ONE_SOL)).unwrap();
+        assert!(partial.not_enough_liquidity);
+        assert!(partial.expected_output > 0);
+
+        // `amount` is the largest input the output vault can still cover.
+        let filled = venue.quote(sol_to_usdc(partial.amount)).unwrap();
+        assert!(!filled.not_enough_liquidity);
+        assert_eq!(filled.expected_output, partial.expected_output);
+        assert!(
+            venue
+                .quote(sol_to_usdc(partial.amount + 1))
+                .unwrap()
+                .not_enough_liquidity
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_fill_is_not_built_as_a_swap() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+
+        let partial = venue.quote(sol_to_usdc(10_000 * ONE_SOL)).unwrap();
+        assert!(partial.not_enough_liquidity);
+        assert_eq!(
+            venue
+                .generate_swap_instruction(sol_to_usdc(10_000 * ONE_SOL), user)
+                .unwrap_err(),
+            TradingVenueError::NotEnoughLiquidity(USDC_MINT.into())
+        );
+
+        // Requoting the fillable amount builds normally.
+        let ix = venue
+            .generate_swap_instruction(sol_to_usdc(partial.amount), user)
+            .unwrap();
+        let data = SwapIxData::try_from_slice(&ix.data[8..]).unwrap();
+        assert_eq!(data.amount_in, partial.amount);
+    }
+
+    #[tokio::test]
+    async fn test_compute_unit_hint() {
+        let venue = OxediumFixture::new().venue().await;
+        assert_eq!(venue.compute_unit_hint(), OXEDIUM_COMPUTE_UNIT_HINT);
+        assert!(venue.compute_unit_hint() > DEFAULT_COMPUTE_UNIT_HINT);
+    }
+
+    #[tokio::test]
+    async fn test_extreme_inputs_never_panic() {
+        let mut extreme = OxediumFixture::new();
+        extreme.vault_mut(&SOL_MINT).initial_liquidity = 0;
+        extreme.vault_mut(&USDC_MINT).base_fee = u64::MAX;
+        extreme.oracle_mut(&SOL_ORACLE).price_message.price = i64::MAX;
+        extreme.mints.insert(USDC_MINT, u8::MAX);
+
+        let mut rng = rand::rng();
+        for fixture in [OxediumFixture::new(), extreme] {
+            let venue = fixture.venue().await;
+            let amounts = (0..64)
+                .map(|shift| 1u64 << shift)
+                .chain((0..256).map(|_| rng.random::<u64>()))
+                .chain([u64::MAX - 1, u64::MAX]);
+
+            for amount in amounts {
+                for (input_mint, output_mint) in [(SOL_MINT, USDC_MINT), (USDC_MINT, SOL_MINT)] {
+                    let request =
+                        QuoteRequest::new(input_mint, output_mint, amount, SwapType::ExactIn);
+                    if let Ok(quote) = venue.quote(request) {
+                        assert!(quote.amount <= amount);
+                    }
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_state_from_accounts_matches_cache_update() {
+        let fixture = OxediumFixture::new();
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        venue
+            .update_state_from_accounts(&fixture.accounts())
+            .unwrap();
+
+        assert!(venue.initialized());
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap(),
+            fixture.venue().await.quote(sol_to_usdc(ONE_SOL)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_swap_account_keys_cover_instruction() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+        let request = sol_to_usdc(ONE_SOL);
+
+        let keys = venue.swap_account_keys(&request, user).unwrap();
+        let ix = venue.generate_swap_instruction(request, user).unwrap();
+
+        assert!(keys.contains(&ix.program_id));
+        for meta in &ix.accounts {
+            assert!(keys.contains(&meta.pubkey), "missing {}", meta.pubkey);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wide_confidence_interval_is_rejected() {
+        let mut fixture = OxediumFixture::new();
+        let price = fixture.oracle_mut(&SOL_ORACLE).price_message.price as u64;
+        // 3% of the price, above the 2% default.
+        fixture.oracle_mut(&SOL_ORACLE).price_message.conf = price * 3 / 100;
+        let mut venue = fixture.venue().await;
+
+        assert_eq!(venue.max_conf_bps, DEFAULT_MAX_CONF_BPS);
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err(),
+            TradingVenueError::OracleConfidenceTooWide(SOL_ORACLE.into())
+        );
+
+        venue.max_conf_bps = 300;
+        assert!(venue.quote(sol_to_usdc(ONE_SOL)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fee_bps_reports_treasury_fee() {
+        let mut fixture = OxediumFixture::new();
+        fixture.treasury.fee_bps = 20;
+
+        let uninitialized =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        assert!(matches!(
+            uninitialized.fee_bps(),
+            Err(TradingVenueError::NotInitialized(_))
+        ));
+
+        assert_eq!(fixture.venue().await.fee_bps().unwrap(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_writable_swap_accounts() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+
+        let writable = venue
+            .writable_swap_accounts(&sol_to_usdc(ONE_SOL), user)
+            .unwrap();
+
+        for key in [
+            user,
+            vault_pda(&SOL_MINT),
+            vault_pda(&USDC_MINT),
+            get_associated_token_address(&user, &SOL_MINT),
+            get_associated_token_address(&user, &USDC_MINT),
+            treasury_pda(),
+        ] {
+            assert!(writable.contains(&key), "missing {key}");
+        }
+        for key in [
+            SOL_MINT,
+            SOL_ORACLE,
+            spl_token::ID,
+            spl_associated_token_account::ID,
+        ] {
+            assert!(!writable.contains(&key), "unexpected {key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_order_is_stable_across_updates() {
+        let fixture = OxediumFixture::new();
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+
+        let mut orders = Vec::new();
+        for _ in 0..3 {
+            venue.update_state(&fixture.cache()).await.unwrap();
+            orders.push(venue.tradable_mints().unwrap());
+        }
+
+        // Registry order, independent of hash map iteration.
+        let registry: Vec<Pubkey> = venue.mint_oracles.iter().map(|(mint, _)| *mint).collect();
+        for order in orders {
+            assert_eq!(order, registry);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_token_by_mint() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let usdc = venue.get_token_by_mint(&USDC_MINT).unwrap();
+        assert_eq!(usdc.pubkey, USDC_MINT);
+        assert_eq!(usdc.decimals, 6);
+
+        let missing = Pubkey::new_unique();
+        assert_eq!(
+            venue.get_token_by_mint(&missing).unwrap_err(),
+            TradingVenueError::InvalidMint(missing.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exact_out_is_rejected() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let request = QuoteRequest::new(SOL_MINT, USDC_MINT, 1_000_000, SwapType::ExactOut);
+        assert!(matches!(
+            venue.quote(request),
+            Err(TradingVenueError::ExactOutNotSupported)
+        ));
+        assert!(venue.bounds_exact_out(0, 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_decimals_before_update_state() {
+        let mut fixture = OxediumFixture::new();
+        let venue = OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        assert!(venue.decimals().unwrap().is_empty());
+
+        let decimals = venue.resolve_decimals(&fixture.cache()).await.unwrap();
+        assert_eq!(decimals, vec![(SOL_MINT, 9), (USDC_MINT, 6)]);
+
+        // Only the first mint loads; the error must name the second one.
+        fixture.mints.remove(&USDC_MINT);
+        assert_eq!(
+            venue.resolve_decimals(&fixture.cache()).await.unwrap_err(),
+            TradingVenueError::NoAccountFound(USDC_MINT.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_math_layer_shortfall_yields_flagged_quote() {
+        let venue = OxediumFixture::new().venue().await;
+        let amount = 10_000 * ONE_SOL;
+
+        let shortfall = compute_swap_math(
+            amount,
+            15_000_000_000,
+            100_000_000,
+            9,
+            6,
+            &venue.vaults[&SOL_MINT],
+            &venue.vaults[&USDC_MINT],
+            &venue.treasury,
+        );
+        assert!(matches!(
+            shortfall,
+            Err(OxediumVenueError::NotEnoughLiquidity)
+        ));
+
+        let quote = venue.quote(sol_to_usdc(amount)).unwrap();
+        assert!(quote.not_enough_liquidity);
+        assert!(quote.amount > 0 && quote.amount < amount);
+    }
+
+    #[test]
+    fn test_oxedium_errors_convert_to_closest_variant() {
+        assert!(matches!(
+            TradingVenueError::from(OxediumVenueError::SwapMathError),
+            TradingVenueError::MathError(_)
+        ));
+        assert!(matches!(
+            TradingVenueError::from(OxediumVenueError::OracleNotFound),
+            TradingVenueError::OracleNotFound
+        ));
+        assert!(matches!(
+            TradingVenueError::from(OxediumVenueError::VaultNotFound),
+            TradingVenueError::VaultNotFound(_)
+        ));
+    }
+
+    #[test]
+    fn test_every_oxedium_error_maps_to_a_fitting_category() {
+        let cases = [
+            (OxediumVenueError::NotInitialized, ErrorCategory::State),
+            (OxediumVenueError::VaultNotFound, ErrorCategory::State),
+            (OxediumVenueError::MintNotFound, ErrorCategory::State),
+            (OxediumVenueError::OracleNotFound, ErrorCategory::State),
+            (OxediumVenueError::TreasuryNotFound, ErrorCategory::State),
+            (OxediumVenueError::AccountNotFound, ErrorCategory::State),
+            (
+                OxediumVenueError::DeserializationError,
+                ErrorCategory::State,
+            ),
+            (OxediumVenueError::SerializationError, ErrorCategory::State),
+            (
+                OxediumVenueError::NotEnoughLiquidity,
+                ErrorCategory::Liquidity,
+            ),
+            (OxediumVenueError::SwapMathError, ErrorCategory::Math),
+            (
+                OxediumVenueError::InvalidArgument("amount"),
+                ErrorCategory::Unsupported,
+            ),
+        ];
+
+        for (error, category) in cases {
+            let rendered = error.to_string();
+            assert_eq!(
+                TradingVenueError::from(error).category(),
+                category,
+                "{rendered}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_swap_instructions_create_user_output_ata() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+
+        let instructions = venue
+            .generate_swap_instructions(sol_to_usdc(ONE_SOL), user)
+            .unwrap();
+        assert_eq!(instructions.len(), 2);
+
+        let create_ata = &instructions[0];
+        assert_eq!(create_ata.program_id, spl_associated_token_account::ID);
+        assert_eq!(
+            create_ata.accounts[1].pubkey,
+            get_associated_token_address(&user, &USDC_MINT)
+        );
+        assert_eq!(
+            instructions[1],
+            venue
+                .generate_swap_instruction(sol_to_usdc(ONE_SOL), user)
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ata_creates_are_idempotent_and_derived() {
+        let mut venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+        let treasury = treasury_pda();
+
+        venue.create_treasury_atas = true;
+        let instructions = venue
+            .generate_swap_instructions(sol_to_usdc(ONE_SOL), user)
+            .unwrap();
+        assert_eq!(instructions.len(), 4);
+
+        let expected = [
+            (user, USDC_MINT),
+            (treasury, SOL_MINT),
+            (treasury, USDC_MINT),
+        ];
+        for (ix, (owner, mint)) in instructions.iter().zip(expected) {
+            assert_eq!(ix.program_id, spl_associated_token_account::ID);
+            // `AssociatedTokenAccountInstruction::CreateIdempotent`
+            assert_eq!(ix.data, vec![1]);
+            assert_eq!(ix.accounts[0].pubkey, user);
+            assert_eq!(
+                ix.accounts[1].pubkey,
+                get_associated_token_address(&owner, &mint)
+            );
+            assert_eq!(ix.accounts[2].pubkey, owner);
+            assert_eq!(ix.accounts[3].pubkey, mint);
+        }
+        assert_eq!(instructions[3].program_id, OXEDIUM_AMM_PROGRAM_ID);
+    }
+
+    #[tokio::test]
+    async fn test_ata_creates_use_token_2022_program() {
+        let fixture = OxediumFixture::new();
+        let mut venue = venue_with_token_2022(&fixture, &[SOL_MINT, USDC_MINT]).await;
+        let user = Pubkey::new_unique();
+        let treasury = treasury_pda();
+
+        venue.create_treasury_atas = true;
+        let instructions = venue
+            .generate_swap_instructions(sol_to_usdc(ONE_SOL), user)
+            .unwrap();
+        assert_eq!(instructions.len(), 4);
+
+        let expected = [
+            (user, USDC_MINT),
+            (treasury, SOL_MINT),
+            (treasury, USDC_MINT),
+        ];
+        for (ix, (owner, mint)) in instructions.iter().zip(expected) {
+            assert_eq!(ix.program_id, spl_associated_token_account::ID);
+            assert_eq!(
+                ix.accounts[1].pubkey,
+                get_associated_token_address_with_program_id(&owner, &mint, &spl_token_2022::ID)
+            );
+            assert_eq!(ix.accounts[5].pubkey, spl_token_2022::ID);
+        }
+
+        // The created output ATA is the one the swap pays into.
+        let swap = &instructions[3];
+        assert_eq!(swap.accounts[6].pubkey, instructions[0].accounts[1].pubkey);
+    }
+
+    #[tokio::test]
+    async fn test_market_label_names_protocol_market_and_mints() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let label = venue.market_label();
+        assert!(label.starts_with(&venue.label()));
+        assert!(label.contains(&venue.market_id().to_string()));
+        assert!(label.ends_with("So11..1112/EPjF..Dt1v"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_update_leaves_state_untouched() {
+        let mut fixture = OxediumFixture::new();
+        let mut venue = fixture.venue().await;
+        let before = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+
+        fixture.vault_mut(&SOL_MINT).current_liquidity /= 2;
+        fixture.treasury.fee_bps += 10;
+        fixture.unix_timestamp += 5;
+        fixture.vault_mut(&USDC_MINT).token_mint = Pubkey::new_unique();
+
+        assert!(venue.update_state(&fixture.cache()).await.is_err());
+
+        assert_eq!(venue.treasury.fee_bps, 0);
+        assert_eq!(venue.clock_unix_timestamp, Some(PUBLISH_TIME + 1));
+        assert_eq!(venue.vaults[&SOL_MINT].current_liquidity, 1_000_000_000_000);
+        assert_eq!(venue.quote(sol_to_usdc(ONE_SOL)).unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn test_token_pair_requires_two_mints() {
+        let mut fixture = OxediumFixture::new();
+        let venue = fixture.venue().await;
+        assert_eq!(venue.token_pair().unwrap(), (SOL_MINT, USDC_MINT));
+
+        let (other_mint, other_oracle) = (Pubkey::new_unique(), Pubkey::new_unique());
+        fixture.add_token(other_mint, other_oracle, 6, 150_000_000_000, 30);
+        fixture
+            .oracles
+            .insert(other_oracle, price_update(100_000_000, -8));
+
+        let mut venue = OxediumAmmVenue::new(
+            fixture.market,
+            vec![
+                (SOL_MINT, SOL_ORACLE),
+                (USDC_MINT, USDC_ORACLE),
+                (other_mint, other_oracle),
+            ],
+        );
+        venue.update_state(&fixture.cache()).await.unwrap();
+
+        assert_eq!(venue.get_token_info().len(), 3);
+        assert!(matches!(
+            venue.token_pair(),
+            Err(TradingVenueError::UnsupportedVenue(_))
+        ));
+    }
+
+    #[test]
+    fn test_vault_round_trips_through_account_data() {
+        let original = vault(SOL_MINT, SOL_ORACLE, 123_456_789, 30);
+        let encoded = borsh::to_vec(&original).unwrap();
+        assert_eq!(encoded.len(), Vault::LEN);
+
+        let mut data = VAULT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&encoded);
+        let decoded = Vault::try_from_account_data(&data).unwrap();
+        assert_eq!(borsh::to_vec(&decoded).unwrap(), encoded);
+
+        // Trailing bytes (account padding) are ignored.
+        data.extend_from_slice(&[0; 16]);
+        assert!(Vault::try_from_account_data(&data).is_ok());
+
+        for len in [
+            0,
+            ANCHOR_DISCRIMINATOR_LEN - 1,
+            ANCHOR_DISCRIMINATOR_LEN + Vault::LEN - 1,
+        ] {
+            assert!(Vault::try_from_account_data(&data[..len]).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wrong_discriminator_is_an_undecodable_account() {
+        let fixture = OxediumFixture::new();
+
+        for target in [vault_pda(&USDC_MINT), treasury_pda(), USDC_ORACLE] {
+            let mut accounts = fixture.accounts();
+            let data = &mut accounts.get_mut(&target).unwrap().data;
+            let wrong = if target == treasury_pda() {
+                VAULT_DISCRIMINATOR
+            } else {
+                TREASURY_DISCRIMINATOR
+            };
+            data[..ANCHOR_DISCRIMINATOR_LEN].copy_from_slice(&wrong);
+
+            let cache: StaticAccountsCache = accounts.into_iter().collect();
+            let mut venue =
+                OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+            venue.update_state(&cache).await.unwrap();
+            assert_eq!(
+                venue.update_warnings(),
+                [UpdateWarning::UndecodableAccount(target)]
+            );
+
+            let mut strict =
+                OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+            strict.strict = true;
+            assert_eq!(
+                strict.update_state(&cache).await.unwrap_err(),
+                TradingVenueError::MissingState(target.into())
+            );
+            assert!(!strict.initialized());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_explicit_min_amount_out_is_serialized() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+
+        for min_out in [0, 1, 123_456, u64::MAX] {
+            let ix = venue
+                .generate_swap_instruction_with_min(sol_to_usdc(ONE_SOL), user, min_out)
+                .unwrap();
+            let data = SwapIxData::try_from_slice(&ix.data[8..]).unwrap();
+
+            assert_eq!(data.amount_in, ONE_SOL);
+            assert_eq!(data.min_amount_out, min_out);
+        }
+
+        // Without an explicit floor, the quoted output less slippage is used.
+        let quote = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        let derived = venue
+            .generate_swap_instruction(sol_to_usdc(ONE_SOL), user)
+            .unwrap();
+        let explicit = venue
+            .generate_swap_instruction_with_min(sol_to_usdc(ONE_SOL), user, quote.expected_output)
+            .unwrap();
+        assert_eq!(derived, explicit);
+    }
+
+    #[tokio::test]
+    async fn test_required_programs_cover_swap_invocations() {
+        let venue = OxediumFixture::new().venue().await;
+        let programs = venue.required_programs();
+
+        assert!(programs.contains(&OXEDIUM_AMM_PROGRAM_ID));
+        assert!(programs.contains(&spl_token::ID));
+
+        let instructions = venue
+            .generate_swap_instructions(sol_to_usdc(ONE_SOL), Pubkey::new_unique())
+            .unwrap();
+        for ix in instructions {
+            assert!(programs.contains(&ix.program_id));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_price_ratio_is_in_atoms_and_reciprocal() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let sol_in_usdc = venue.price_ratio(&SOL_MINT, &USDC_MINT).unwrap();
+        let usdc_in_sol = venue.price_ratio(&USDC_MINT, &SOL_MINT).unwrap();
+
+        // 150 USDC per SOL, i.e. 150e6 USDC atoms per 1e9 lamports.
+        assert!((sol_in_usdc - 0.15).abs() < 1e-12);
+        assert!((sol_in_usdc * usdc_in_sol - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_price_ratio_rejects_unknown_mint() {
+        let venue = OxediumFixture::new().venue().await;
+        let unknown = Pubkey::new_unique();
+
+        assert_eq!(
+            venue.price_ratio(&unknown, &USDC_MINT).unwrap_err(),
+            TradingVenueError::InvalidMint(unknown.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_full_update_records_no_warnings() {
+        let venue = OxediumFixture::new().venue().await;
+        assert!(venue.update_warnings().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_oracle_is_recorded_as_warning() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracles.remove(&USDC_ORACLE);
+        let venue = fixture.venue().await;
+
+        assert_eq!(
+            venue.update_warnings(),
+            [UpdateWarning::MissingAccount(USDC_ORACLE)]
+        );
+        assert!(venue.get_token_by_mint(&USDC_MINT).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_undecodable_oracle_is_recorded_as_warning() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracles.remove(&SOL_ORACLE);
+        fixture.extra.insert(
+            SOL_ORACLE,
+            Account {
+                data: PRICE_UPDATE_V2_DISCRIMINATOR.to_vec(),
+                ..Account::default()
+            },
+        );
+        let venue = fixture.venue().await;
+
+        assert_eq!(
+            venue.update_warnings(),
+            [UpdateWarning::UndecodableAccount(SOL_ORACLE)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_warnings_reset_on_successful_update() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracles.remove(&USDC_ORACLE);
+        let mut venue = fixture.venue().await;
+        assert_eq!(venue.update_warnings().len(), 1);
+
+        venue
+            .update_state(&OxediumFixture::new().cache())
+            .await
+            .unwrap();
+        assert!(venue.update_warnings().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_state_requests_exactly_declared_keys() {
+        let fixture = OxediumFixture::new();
+        let cache = RecordingAccountsCache::new(fixture.cache());
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+
+        venue.update_state(&cache).await.unwrap();
+
+        let declared = venue.get_required_pubkeys_for_update().unwrap();
+        assert_eq!(declared.len(), 8);
+        assert_eq!(cache.requested_keys(), declared);
+    }
+
+    #[tokio::test]
+    async fn test_required_pubkeys_have_stable_documented_order() {
+        let venue = OxediumFixture::new().venue().await;
+        let keys = venue.get_required_pubkeys_for_update().unwrap();
+
+        assert_eq!(
+            keys,
+            vec![
+                clock::ID,
+                treasury_pda(),
+                vault_pda(&SOL_MINT),
+                SOL_MINT,
+                SOL_ORACLE,
+                vault_pda(&USDC_MINT),
+                USDC_MINT,
+                USDC_ORACLE,
+            ]
+        );
+        for _ in 0..10 {
+            assert_eq!(venue.get_required_pubkeys_for_update().unwrap(), keys);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_loses_fees() {
+        let venue = OxediumFixture::new().venue().await;
+
+        for amount in [ONE_SOL / 100, ONE_SOL, 10 * ONE_SOL] {
+            let back = venue.round_trip_quote(SOL_MINT, USDC_MINT, amount).unwrap();
+            assert!(back > 0);
+            assert!(back < amount, "round trip of {amount} returned {back}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_matches_two_quotes() {
+        let venue = OxediumFixture::new().venue().await;
+        let there = venue.quote(sol_to_usdc(ONE_SOL)).unwrap();
+        let back = venue
+            .quote(QuoteRequest::new(
+                USDC_MINT,
+                SOL_MINT,
+                there.expected_output,
+                SwapType::ExactIn,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            venue
+                .round_trip_quote(SOL_MINT, USDC_MINT, ONE_SOL)
+                .unwrap(),
+            back.expected_output
+        );
+    }
+
+    #[tokio::test]
+    async fn test_min_liquidity_threshold() {
+        let mut venue = OxediumFixture::new().venue().await;
+        assert!(venue.meets_liquidity_threshold());
+
+        // The USDC vault holds 150_000_000_000 atoms.
+        venue.min_liquidity = 150_000_000_000;
+        assert!(venue.meets_liquidity_threshold());
+        assert!(venue.is_tradable());
+
+        venue.min_liquidity = 150_000_000_001;
+        assert!(!venue.meets_liquidity_threshold());
+        assert!(!venue.is_tradable());
+    }
+
+    #[tokio::test]
+    async fn test_quote_with_current_liquidity_matches_quote() {
+        let venue = OxediumFixture::new().venue().await;
+        let liquidity_in = venue.vaults[&SOL_MINT].current_liquidity;
+        let liquidity_out = venue.vaults[&USDC_MINT].current_liquidity;
+
+        assert_eq!(
+            venue
+                .quote_with_liquidity(sol_to_usdc(ONE_SOL), liquidity_in, liquidity_out)
+                .unwrap(),
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deeper_hypothetical_liquidity_lowers_impact() {
+        let venue = OxediumFixture::new().venue().await;
+        let liquidity_in = venue.vaults[&SOL_MINT].current_liquidity;
+        let liquidity_out = venue.vaults[&USDC_MINT].current_liquidity;
+
+        // 150 SOL is 22_500 USDC, over 10% of the 150_000 USDC vault.
+        let request = sol_to_usdc(150 * ONE_SOL);
+        let shallow = venue.quote(request.clone()).unwrap();
+        let deep = venue
+            .quote_with_liquidity(request, 2 * liquidity_in, 2 * liquidity_out)
+            .unwrap();
+
+        assert!(deep.price_impact_bps < shallow.price_impact_bps);
+        assert!(deep.expected_output > shallow.expected_output);
+        // The venue itself is untouched.
+        assert_eq!(venue.vaults[&USDC_MINT].current_liquidity, liquidity_out);
+    }
+
+    #[tokio::test]
+    async fn test_unloaded_oracle_is_missing_oracle() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracles.remove(&USDC_ORACLE);
+        let venue = fixture.venue().await;
+
+        let error = venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err();
+        assert_eq!(error, TradingVenueError::MissingOracle(USDC_ORACLE.into()));
+        assert_eq!(error.category(), ErrorCategory::State);
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_mint_is_oracle_not_found() {
+        let venue = OxediumFixture::new().venue().await;
+        let unregistered = Pubkey::new_unique();
+
+        assert_eq!(
+            venue
+                .get_swap_accounts(SOL_MINT, unregistered, Pubkey::new_unique())
+                .unwrap_err(),
+            TradingVenueError::OracleNotFound
+        );
+        assert_eq!(
+            venue
+                .quote(QuoteRequest::new(
+                    SOL_MINT,
+                    unregistered,
+                    ONE_SOL,
+                    SwapType::ExactIn
+                ))
+                .unwrap_err(),
+            TradingVenueError::InvalidMint(unregistered.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bounds_cover_every_pair_of_a_three_mint_venue() {
+        let bonk_mint = Pubkey::new_unique();
+        let bonk_oracle = Pubkey::new_unique();
+
+        let mut fixture = OxediumFixture::new();
+        fixture.add_token(bonk_mint, bonk_oracle, 5, 1_000_000_000_000, 30);
+        fixture.oracles.insert(bonk_oracle, price_update(2_000, -8));
+
+        let registry = vec![
+            (SOL_MINT, SOL_ORACLE),
+            (USDC_MINT, USDC_ORACLE),
+            (bonk_mint, bonk_oracle),
+        ];
+        let mut venue = OxediumAmmVenue::new(fixture.market, registry);
+        venue.update_state(&fixture.cache()).await.unwrap();
+
+        let pairs = venue.all_pairs();
+        assert_eq!(pairs, vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]);
+        for (tkn_in, tkn_out) in pairs {
+            let (lower, upper) = venue.bounds(tkn_in, tkn_out).unwrap();
+            assert!(lower <= upper, "pair ({tkn_in}, {tkn_out})");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bounds_reject_identical_indices() {
+        let venue = OxediumFixture::new().venue().await;
+
+        assert_eq!(
+            venue.bounds(0, 0).unwrap_err(),
+            TradingVenueError::InvalidMint(SOL_MINT.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_safe_input_is_the_full_fill_boundary() {
+        let venue = OxediumFixture::new().venue().await;
+
+        for (input_mint, output_mint) in [(SOL_MINT, USDC_MINT), (USDC_MINT, SOL_MINT)] {
+            let max = venue.max_safe_input(input_mint, output_mint).unwrap();
+            assert!(max > 0);
+
+            let full = venue
+                .quote(QuoteRequest::new(
+                    input_mint,
+                    output_mint,
+                    max,
+                    SwapType::ExactIn,
+                ))
+                .unwrap();
+            assert!(!full.not_enough_liquidity);
+            assert_eq!(full.amount, max);
+
+            let over = venue
+                .quote(QuoteRequest::new(
+                    input_mint,
+                    output_mint,
+                    max + 1,
+                    SwapType::ExactIn,
+                ))
+                .unwrap();
+            assert!(over.not_enough_liquidity);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_safe_input_respects_fee_limits() {
+        let mut fixture = OxediumFixture::new();
+        // Above 10% of the vault the fee is multiplied past 100%.
+        fixture.vault_mut(&USDC_MINT).base_fee = 1_500;
+        let venue = fixture.venue().await;
+
+        let max = venue.max_safe_input(SOL_MINT, USDC_MINT).unwrap();
+        assert!(max > 0);
+
+        let full = venue.quote(sol_to_usdc(max)).unwrap();
+        assert!(!full.not_enough_liquidity);
+        assert_eq!(full.amount, max);
+        assert!(venue.quote(sol_to_usdc(max + 1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_max_safe_input_rejects_unknown_mint() {
+        let venue = OxediumFixture::new().venue().await;
+        let unknown = Pubkey::new_unique();
+
+        assert_eq!(
+            venue.max_safe_input(SOL_MINT, unknown).unwrap_err(),
+            TradingVenueError::InvalidMint(unknown.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lenient_update_skips_missing_oracle() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracles.remove(&USDC_ORACLE);
+
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        assert!(!venue.strict);
+        venue.update_state(&fixture.cache()).await.unwrap();
+
+        assert!(venue.initialized());
+        assert_eq!(venue.tradable_mints().unwrap(), vec![SOL_MINT]);
+    }
+
+    #[tokio::test]
+    async fn test_accounts_missing_from_next_update_are_dropped() {
+        let fixture = OxediumFixture::new();
+        let mut venue = fixture.venue().await;
+        assert!(venue.oracles.contains_key(&USDC_ORACLE));
+
+        let mut accounts = fixture.accounts();
+        accounts.remove(&USDC_ORACLE);
+        accounts.remove(&vault_pda(&SOL_MINT));
+        venue.update_state_from_accounts(&accounts).unwrap();
+
+        assert!(!venue.oracles.contains_key(&USDC_ORACLE));
+        assert!(!venue.vaults.contains_key(&SOL_MINT));
+        assert!(venue.tradable_mints().unwrap().is_empty());
+        assert_eq!(
+            venue.quote(sol_to_usdc(ONE_SOL)).unwrap_err(),
+            TradingVenueError::InvalidMint(SOL_MINT.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strict_update_fails_on_missing_oracle() {
+        let mut fixture = OxediumFixture::new();
+        fixture.oracles.remove(&USDC_ORACLE);
+
+        let mut venue =
+            OxediumAmmVenue::from_account(&fixture.market, &Account::default()).unwrap();
+        venue.strict = true;
+
+        assert_eq!(
+            venue.update_state(&fixture.cache()).await.unwrap_err(),
+            TradingVenueError::MissingState(USDC_ORACLE.into())
+        );
+        // Nothing from the rejected update was applied.
+        assert!(!venue.initialized());
+        assert!(venue.vaults.is_empty());
+        assert!(venue.mints.is_empty());
+        assert_eq!(venue.last_updated_slot(), None);
+
+        venue
+            .update_state(&OxediumFixture::new().cache())
+            .await
+            .unwrap();
+        assert!(venue.initialized());
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "pubsub")]
+
+//! Live websocket tests. Set `PUBSUB_WS_URL` and `PUBSUB_RPC_URL` to run them;
+//! without both they pass trivially.
+
+#[cfg(test)]
+mod pubsub_cache {
+    use std::time::Duration;
+
+    use solana_pubkey::Pubkey;
+    use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    use solana_sysvar::clock::{self, Clock};
+
+    use titan_integration_template::account_caching::{
+        AccountsCache, pubsub_cache::PubsubAccountsCache,
+    };
+
+    fn endpoints() -> Option<(String, String)> {
+        Some((
+            std::env::var("PUBSUB_WS_URL").ok()?,
+            std::env::var("PUBSUB_RPC_URL").ok()?,
+        ))
+    }
+
+    async fn clock_slot(cache: &PubsubAccountsCache) -> u64 {
+        let account = cache.get_account(&clock::ID).await.unwrap().unwrap();
+        bincode::deserialize::<Clock>(&account.data).unwrap().slot
+    }
+
+    #[tokio::test]
+    async fn test_subscribed_account_stays_live() {
+        let Some((ws_url, rpc_url)) = endpoints() else {
+            return;
+        };
+        let cache = PubsubAccountsCache::new(&ws_url, RpcClient::new(rpc_url))
+            .await
+            .unwrap();
+
+        cache.subscribe(&[clock::ID]).await.unwrap();
+        assert!(cache.is_subscribed(&clock::ID));
+
+        // The clock advances every slot, so a live entry moves on its own.
+        let first = clock_slot(&cache).await;
+        let mut latest = first;
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            latest = clock_slot(&cache).await;
+            if latest > first {
+                break;
+            }
+        }
+        assert!(latest > first);
+
+        // Unsubscribed keys are served through the RPC fallback.
+        let unknown = Pubkey::new_unique();
+        assert!(!cache.is_subscribed(&unknown));
+        assert!(cache.get_account(&unknown).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ended_subscription_reads_fresh_state() {
+        let Some((ws_url, rpc_url)) = endpoints() else {
+            return;
+        };
+        let cache = PubsubAccountsCache::new(&ws_url, RpcClient::new(rpc_url))
+            .await
+            .unwrap();
+
+        cache.subscribe(&[clock::ID]).await.unwrap();
+        let seeded = clock_slot(&cache).await;
+        let mut live = seeded;
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            live = clock_slot(&cache).await;
+            if live > seeded {
+                break;
+            }
+        }
+        assert!(live > seeded);
+
+        // Once the stream ends, reads go to RPC instead of returning the
+        // snapshot taken when subscribing.
+        cache.unsubscribe(&[clock::ID]);
+        assert!(!cache.is_subscribed(&clock::ID));
+        assert!(clock_slot(&cache).await >= live);
+
+        // An ended subscription can be opened again.
+        cache.subscribe(&[clock::ID]).await.unwrap();
+        assert!(cache.is_subscribed(&clock::ID));
+    }
+}
@@ -0,0 +1,91 @@
+mod common;
+
+#[cfg(test)]
+mod quote_cache {
+    use titan_integration_template::trading_venue::{
+        QuoteRequest, SwapType, TradingVenue, quote_cache::QuoteCache,
+    };
+
+    use crate::common::{OxediumFixture, SOL_MINT, USDC_MINT};
+
+    fn sol_to_usdc(amount: u64) -> QuoteRequest {
+        QuoteRequest::new(SOL_MINT, USDC_MINT, amount, SwapType::ExactIn)
+    }
+
+    #[tokio::test]
+    async fn test_repeated_quotes_hit_until_state_update() {
+        let mut fixture = OxediumFixture::new();
+        let mut cache = QuoteCache::new(Box::new(fixture.venue().await));
+
+        let first = cache.quote(sol_to_usdc(1_000_000_000)).unwrap();
+        assert_eq!(cache.hits(), 0);
+        for _ in 0..3 {
+            assert_eq!(cache.quote(sol_to_usdc(1_000_000_000)).unwrap(), first);
+        }
+        assert_eq!(cache.hits(), 3);
+
+        cache.quote(sol_to_usdc(2_000_000_000)).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // An update at the same slot keeps the cache.
+        cache
+            .venue_mut()
+            .update_state(&fixture.cache())
+            .await
+            .unwrap();
+        cache.quote(sol_to_usdc(1_000_000_000)).unwrap();
+        assert_eq!(cache.hits(), 4);
+
+        fixture.slot += 1;
+        fixture.vault_mut(&USDC_MINT).current_liquidity /= 2;
+        cache
+            .venue_mut()
+            .update_state(&fixture.cache())
+            .await
+            .unwrap();
+
+        let fresh = cache.quote(sol_to_usdc(1_000_000_000)).unwrap();
+        assert_eq!(cache.hits(), 4);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            fresh,
+            cache.venue().quote(sol_to_usdc(1_000_000_000)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let cache = QuoteCache::new(Box::new(OxediumFixture::new().venue().await));
+
+        let same_mint = QuoteRequest::new(SOL_MINT, SOL_MINT, 1, SwapType::ExactIn);
+        assert!(cache.quote(same_mint.clone()).is_err());
+        assert!(cache.quote(same_mint).is_err());
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_oldest_quote() {
+        let cache = QuoteCache::new(Box::new(OxediumFixture::new().venue().await)).with_capacity(2);
+
+        for amount in [1, 2, 3] {
+            cache.quote(sol_to_usdc(amount * 1_000_000_000)).unwrap();
+        }
+        assert_eq!(cache.len(), 2);
+
+        // The newest quotes are kept; the oldest is requoted.
+        cache.quote(sol_to_usdc(3_000_000_000)).unwrap();
+        assert_eq!(cache.hits(), 1);
+        cache.quote(sol_to_usdc(1_000_000_000)).unwrap();
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.len(), 2);
+
+        let disabled =
+            QuoteCache::new(Box::new(OxediumFixture::new().venue().await)).with_capacity(0);
+        disabled.quote(sol_to_usdc(1_000_000_000)).unwrap();
+        disabled.quote(sol_to_usdc(1_000_000_000)).unwrap();
+        assert!(disabled.is_empty());
+        assert_eq!(disabled.hits(), 0);
+    }
+}
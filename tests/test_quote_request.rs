@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod quote_request {
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::trading_venue::{
+        QuoteRequest, SwapType, error::TradingVenueError,
+    };
+
+    #[test]
+    fn test_builder_defaults_to_exact_in() {
+        let input_mint = Pubkey::new_unique();
+        let output_mint = Pubkey::new_unique();
+
+        let request = QuoteRequest::builder()
+            .input_mint(input_mint)
+            .output_mint(output_mint)
+            .amount(1_000)
+            .slippage_bps(50)
+            .build()
+            .unwrap();
+
+        assert_eq!(request.input_mint, input_mint);
+        assert_eq!(request.output_mint, output_mint);
+        assert_eq!(request.amount, 1_000);
+        assert_eq!(request.swap_type, SwapType::ExactIn);
+        assert_eq!(request.slippage_bps, 50);
+    }
+
+    #[test]
+    fn test_builder_exact_out() {
+        let request = QuoteRequest::builder()
+            .input_mint(Pubkey::new_unique())
+            .output_mint(Pubkey::new_unique())
+            .exact_out()
+            .build()
+            .unwrap();
+
+        assert_eq!(request.swap_type, SwapType::ExactOut);
+        assert_eq!(request.amount, 0);
+        assert_eq!(request.slippage_bps, 0);
+    }
+
+    #[test]
+    fn test_builder_requires_both_mints() {
+        let missing_output = QuoteRequest::builder()
+            .input_mint(Pubkey::new_unique())
+            .build();
+
+        assert!(matches!(
+            missing_output,
+            Err(TradingVenueError::InvalidMint(_))
+        ));
+    }
+}
@@ -78,22 +78,11 @@ mod simulations {
         litesvm: &mut LiteSVM,
         keypair: &Keypair,
     ) -> u64 {
-        let tradable_mints = venue.get_token_info();
+        let token_a_info = venue.get_token_by_mint(&request.input_mint).unwrap();
+        let token_b_info = venue.get_token_by_mint(&request.output_mint).unwrap();
 
-        let idx_0 = tradable_mints
-            .iter()
-            .position(|x| x.pubkey == request.input_mint)
-            .unwrap();
-        let idx_1 = (idx_0 + 1) % 2;
-
-        let (token_a, token_a_program) = (
-            tradable_mints[idx_0].pubkey,
-            tradable_mints[idx_0].get_token_program(),
-        );
-        let (token_b, token_b_program) = (
-            tradable_mints[idx_1].pubkey,
-            tradable_mints[idx_1].get_token_program(),
-        );
+        let (token_a, token_a_program) = (token_a_info.pubkey, token_a_info.get_token_program());
+        let (token_b, token_b_program) = (token_b_info.pubkey, token_b_info.get_token_program());
 
         let token_account_a = get_associated_token_address_with_program_id(
             &keypair.pubkey(),
@@ -202,6 +191,7 @@ mod simulations {
                     output_mint: venue.get_token(out_idx).unwrap().pubkey,
                     amount: bound,
                     swap_type: SwapType::ExactIn,
+                    slippage_bps: 0,
                 };
 
                 let sim =
@@ -255,6 +245,7 @@ mod simulations {
                     output_mint: venue.get_token(out_idx as usize).unwrap().pubkey,
                     amount,
                     swap_type: SwapType::ExactIn,
+                    slippage_bps: 0,
                 };
 
                 let sim =
@@ -299,6 +290,7 @@ mod simulations {
                         output_mint: token_info[out_idx as usize].pubkey,
                         amount,
                         swap_type: SwapType::ExactIn,
+                        slippage_bps: 0,
                     })
                     .unwrap();
 
@@ -339,6 +331,7 @@ mod simulations {
                         output_mint: token_info[out_idx as usize].pubkey,
                         amount,
                         swap_type: SwapType::ExactIn,
+                        slippage_bps: 0,
                     })
                     .unwrap();
             }
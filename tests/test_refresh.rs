@@ -0,0 +1,47 @@
+mod common;
+
+#[cfg(test)]
+mod refresh {
+    use solana_account::Account;
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::{
+        account_caching::rpc_cache::RpcClientCache,
+        oxedium::amm::OxediumAmmVenue,
+        trading_venue::{FromAccount, TradingVenue, refresh::refresh_all},
+    };
+
+    use crate::common::{OxediumFixture, mock_rpc::MockRpc};
+
+    #[tokio::test]
+    async fn test_refresh_all_shares_one_fetch() {
+        let fixture = OxediumFixture::new();
+        let rpc = MockRpc::new();
+        for (pubkey, account) in fixture.accounts() {
+            rpc.insert(pubkey, account);
+        }
+        let cache = RpcClientCache::new(rpc.client());
+
+        let mut venues: Vec<Box<dyn TradingVenue>> = (0..2)
+            .map(|_| {
+                let venue =
+                    OxediumAmmVenue::from_account(&Pubkey::new_unique(), &Account::default())
+                        .unwrap();
+                Box::new(venue) as Box<dyn TradingVenue>
+            })
+            .collect();
+
+        let results = refresh_all(&mut venues, &cache).await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert!(venues.iter().all(|venue| venue.initialized()));
+
+        // Both venues share the registry, so one deduplicated batch covers them.
+        let calls = rpc.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(
+            calls[0].1,
+            venues[0].get_required_pubkeys_for_update().unwrap().len()
+        );
+    }
+}
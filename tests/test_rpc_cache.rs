@@ -0,0 +1,287 @@
+mod common;
+
+#[cfg(test)]
+mod rpc_cache {
+    use std::time::Duration;
+
+    use solana_account::Account;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::account_caching::{
+        AccountCacheError, AccountsCache,
+        rpc_cache::{MAX_MULTIPLE_ACCOUNTS, RetryPolicy, RpcClientCache},
+    };
+
+    use crate::common::mock_rpc::MockRpc;
+
+    #[tokio::test]
+    async fn test_get_accounts_chunks_rpc_calls() {
+        let rpc = MockRpc::new();
+        let cache = RpcClientCache::new(rpc.client());
+
+        let pubkeys: Vec<Pubkey> = (0..250).map(|_| Pubkey::new_unique()).collect();
+        rpc.insert(pubkeys[150], Account::new(42, 0, &Pubkey::default()));
+
+        let accounts = cache.get_accounts(&pubkeys).await.unwrap();
+        assert_eq!(accounts.len(), pubkeys.len());
+        assert_eq!(accounts[150].as_ref().unwrap().lamports, 42);
+        assert!(accounts[149].is_none());
+
+        let mut batch_sizes: Vec<usize> = rpc
+            .calls()
+            .into_iter()
+            .map(|(request, keys)| {
+                assert_eq!(request, RpcRequest::GetMultipleAccounts);
+                keys
+            })
+            .collect();
+        batch_sizes.sort();
+
+        assert_eq!(
+            batch_sizes,
+            vec![50, MAX_MULTIPLE_ACCOUNTS, MAX_MULTIPLE_ACCOUNTS]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_below_slot() {
+        let cache = RpcClientCache::new(MockRpc::new().client());
+
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for (key, slot) in keys.iter().zip([10, 20, 30]) {
+            cache.insert(*key, Some(Account::new(slot, 0, &Pubkey::default())), slot);
+        }
+
+        cache.invalidate_below_slot(20);
+
+        assert_eq!(cache.cached_slot(&keys[0]), None);
+        assert_eq!(cache.cached_slot(&keys[1]), Some(20));
+        assert_eq!(cache.cached_slot(&keys[2]), Some(30));
+    }
+
+    #[tokio::test]
+    async fn test_fetched_entries_record_context_slot() {
+        let rpc = MockRpc::new();
+        rpc.set_slot(77);
+
+        let found = Pubkey::new_unique();
+        let missing = Pubkey::new_unique();
+        rpc.insert(found, Account::new(1, 0, &Pubkey::default()));
+
+        let cache = RpcClientCache::new(rpc.client());
+        assert!(cache.get_account(&found).await.unwrap().is_some());
+        assert!(cache.get_accounts(&[missing]).await.unwrap()[0].is_none());
+
+        assert_eq!(cache.cached_slot(&found), Some(77));
+        assert_eq!(cache.cached_slot(&missing), Some(77));
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_least_recently_used() {
+        let max_entries = 3;
+        let cache = RpcClientCache::new(MockRpc::new().client()).with_capacity(max_entries);
+
+        let keys: Vec<Pubkey> = (0..=max_entries).map(|_| Pubkey::new_unique()).collect();
+        for key in &keys {
+            cache.insert(*key, None, 1);
+        }
+
+        assert_eq!(cache.cached_slot(&keys[0]), None);
+        for key in &keys[1..] {
+            assert_eq!(cache.cached_slot(key), Some(1));
+        }
+
+        // Reading an entry refreshes its recency.
+        cache.get_multiple(&keys[1..2]);
+        cache.insert(Pubkey::new_unique(), None, 1);
+
+        assert_eq!(cache.cached_slot(&keys[1]), Some(1));
+        assert_eq!(cache.cached_slot(&keys[2]), None);
+    }
+
+    #[tokio::test]
+    async fn test_eviction_frees_a_batch_of_oldest_entries() {
+        let max_entries = 100;
+        let cache = RpcClientCache::new(MockRpc::new().client()).with_capacity(max_entries);
+
+        let keys: Vec<Pubkey> = (0..=max_entries).map(|_| Pubkey::new_unique()).collect();
+        for key in &keys {
+            cache.insert(*key, None, 1);
+        }
+
+        // Overflowing by one evicts down to 90% of the bound, oldest first.
+        let cached: Vec<bool> = keys
+            .iter()
+            .map(|key| cache.cached_slot(key).is_some())
+            .collect();
+        assert!(cached[..11].iter().all(|cached| !cached));
+        assert!(cached[11..].iter().all(|cached| *cached));
+    }
+
+    #[tokio::test]
+    async fn test_invalidated_entry_is_refetched() {
+        let rpc = MockRpc::new();
+        let key = Pubkey::new_unique();
+        rpc.set_slot(7);
+        let cache = RpcClientCache::new(rpc.client());
+
+        cache.insert(key, None, 1);
+        cache.invalidate(&key);
+        assert_eq!(cache.cached_slot(&key), None);
+
+        assert!(cache.get_account(&key).await.unwrap().is_none());
+        assert_eq!(cache.cached_slot(&key), Some(7));
+    }
+
+    fn fast_retries(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried() {
+        let rpc = MockRpc::new();
+        let key = Pubkey::new_unique();
+        rpc.insert(key, Account::new(7, 0, &Pubkey::default()));
+        rpc.fail_next(2);
+
+        let cache = RpcClientCache::new(rpc.client()).with_retry_policy(fast_retries(3));
+        let account = cache.get_account(&key).await.unwrap().unwrap();
+
+        assert_eq!(account.lamports, 7);
+        assert_eq!(rpc.calls().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_are_bounded() {
+        let rpc = MockRpc::new();
+        rpc.fail_next(5);
+
+        let cache = RpcClientCache::new(rpc.client()).with_retry_policy(fast_retries(3));
+        let result = cache.get_accounts(&[Pubkey::new_unique()]).await;
+
+        assert!(matches!(
+            result,
+            Err(AccountCacheError::FailedToFetchAccount(_))
+        ));
+        assert_eq!(rpc.calls().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_warm_makes_get_accounts_cache_only() {
+        let rpc = MockRpc::new();
+        let cache = RpcClientCache::new(rpc.client());
+
+        let pubkeys: Vec<Pubkey> = (0..150).map(|_| Pubkey::new_unique()).collect();
+        rpc.insert(pubkeys[0], Account::new(5, 0, &Pubkey::default()));
+
+        cache.warm(&pubkeys).await.unwrap();
+        assert_eq!(rpc.calls().len(), 2);
+        rpc.clear_calls();
+
+        let accounts = cache.get_accounts(&pubkeys).await.unwrap();
+        assert_eq!(accounts[0].as_ref().unwrap().lamports, 5);
+        assert!(accounts[1..].iter().all(Option::is_none));
+        assert!(rpc.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_uncached_read_replaces_stale_entry() {
+        let rpc = MockRpc::new();
+        rpc.set_slot(50);
+        let key = Pubkey::new_unique();
+        rpc.insert(key, Account::new(2, 0, &Pubkey::default()));
+
+        let cache = RpcClientCache::new(rpc.client());
+        cache.insert(key, Some(Account::new(1, 0, &Pubkey::default())), 10);
+
+        let cached = cache.get_account(&key).await.unwrap().unwrap();
+        assert_eq!(cached.lamports, 1);
+        assert!(rpc.calls().is_empty());
+
+        let fresh = cache.get_account_uncached(&key).await.unwrap().unwrap();
+        assert_eq!(fresh.lamports, 2);
+        assert_eq!(rpc.calls().len(), 1);
+
+        assert_eq!(cache.get_account(&key).await.unwrap().unwrap().lamports, 2);
+        assert_eq!(cache.cached_slot(&key), Some(50));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_pubkeys_share_one_result() {
+        let rpc = MockRpc::new();
+        let (found, missing) = (Pubkey::new_unique(), Pubkey::new_unique());
+        rpc.insert(found, Account::new(3, 0, &Pubkey::default()));
+
+        let cache = RpcClientCache::new(rpc.client());
+        let accounts = cache
+            .get_accounts(&[found, missing, found, missing, found])
+            .await
+            .unwrap();
+
+        assert_eq!(accounts.len(), 5);
+        for i in [0, 2, 4] {
+            assert_eq!(accounts[i].as_ref().unwrap().lamports, 3);
+        }
+        assert!(accounts[1].is_none() && accounts[3].is_none());
+        assert_eq!(rpc.calls(), vec![(RpcRequest::GetMultipleAccounts, 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_short_rpc_response_is_an_error() {
+        let rpc = MockRpc::new();
+        rpc.truncate_next(1);
+        let pubkeys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        let cache = RpcClientCache::new(rpc.client());
+        let result = cache.get_accounts(&pubkeys).await;
+
+        assert!(matches!(
+            result,
+            Err(AccountCacheError::ResponseLengthMismatch {
+                requested: 3,
+                returned: 2
+            })
+        ));
+        assert!(pubkeys.iter().all(|key| cache.cached_slot(key).is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_get_account_with_slot_threads_context_slot() {
+        let rpc = MockRpc::new();
+        rpc.set_slot(42);
+
+        let pubkey = Pubkey::new_unique();
+        rpc.insert(pubkey, Account::new(7, 0, &Pubkey::default()));
+        let cache = RpcClientCache::new(rpc.client());
+
+        let (account, slot) = cache.get_account_with_slot(&pubkey).await.unwrap();
+        assert_eq!(account.unwrap().lamports, 7);
+        assert_eq!(slot, 42);
+
+        // A cache hit reports the slot the entry was fetched at.
+        rpc.set_slot(50);
+        rpc.clear_calls();
+        let (_, slot) = cache.get_account_with_slot(&pubkey).await.unwrap();
+        assert_eq!(slot, 42);
+        assert!(rpc.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_account_with_slot_reports_missing_accounts() {
+        let rpc = MockRpc::new();
+        rpc.set_slot(9);
+        let cache = RpcClientCache::new(rpc.client());
+
+        let (account, slot) = cache
+            .get_account_with_slot(&Pubkey::new_unique())
+            .await
+            .unwrap();
+        assert!(account.is_none());
+        assert_eq!(slot, 9);
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "serde")]
+
+#[cfg(test)]
+mod serde_round_trip {
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::interop::jupiter::{JupiterQuote, to_jupiter_quote};
+    use titan_integration_template::trading_venue::{
+        FeeBreakdown, QuoteRequest, QuoteResult, SwapType, token_info::TokenInfo,
+    };
+
+    #[test]
+    fn test_quote_result_json_round_trip() {
+        let result = QuoteResult {
+            input_mint: Pubkey::new_unique(),
+            output_mint: Pubkey::new_unique(),
+            amount: 1_000_000_000,
+            expected_output: 149_550_000,
+            fee_amount: 3_000_000,
+            fees: FeeBreakdown {
+                protocol_fee: 1_000_000,
+                lp_fee: 2_000_000,
+                transfer_fee: 0,
+            },
+            price_impact_bps: 30,
+            not_enough_liquidity: false,
+        };
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["input_mint"], result.input_mint.to_string());
+
+        let decoded: QuoteResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, result);
+    }
+
+    #[test]
+    fn test_request_and_token_info_json_round_trip() {
+        let request = QuoteRequest::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            42,
+            SwapType::ExactIn,
+        );
+        let json = serde_json::to_string(&request).unwrap();
+        let decoded: QuoteRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.input_mint, request.input_mint);
+        assert_eq!(decoded.swap_type, SwapType::ExactIn);
+
+        let token = TokenInfo {
+            pubkey: Pubkey::new_unique(),
+            decimals: 6,
+            is_token_2022: true,
+            transfer_fee: Some(25),
+            maximum_fee: Some(1_000),
+        };
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(serde_json::from_str::<TokenInfo>(&json).unwrap(), token);
+
+        assert!(
+            serde_json::from_str::<TokenInfo>(&json.replace(&token.pubkey.to_string(), "nope"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_jupiter_quote_matches_schema() {
+        let mut request = QuoteRequest::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1_000_000_000,
+            SwapType::ExactIn,
+        );
+        request.slippage_bps = 50;
+        let result = QuoteResult {
+            input_mint: request.input_mint,
+            output_mint: request.output_mint,
+            amount: request.amount,
+            expected_output: 149_550_000,
+            fee_amount: 3_000_000,
+            fees: FeeBreakdown::default(),
+            price_impact_bps: 30,
+            not_enough_liquidity: false,
+        };
+
+        let quote = to_jupiter_quote(&request, &result, "Oxedium");
+        let json = serde_json::to_value(&quote).unwrap();
+
+        assert_eq!(json["inputMint"], request.input_mint.to_string());
+        assert_eq!(json["outputMint"], request.output_mint.to_string());
+        assert_eq!(json["inAmount"], "1000000000");
+        assert_eq!(json["outAmount"], "149550000");
+        assert_eq!(json["otherAmountThreshold"], "148802250");
+        assert_eq!(json["swapMode"], "ExactIn");
+        assert_eq!(json["slippageBps"], 50);
+        assert_eq!(json["priceImpactPct"], "0.003");
+
+        let market = &json["marketInfos"][0];
+        assert_eq!(market["label"], "Oxedium");
+        assert_eq!(market["notEnoughLiquidity"], false);
+        assert_eq!(market["lpFee"]["amount"], "3000000");
+        assert_eq!(market["lpFee"]["mint"], request.input_mint.to_string());
+
+        let decoded: JupiterQuote = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, quote);
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod slippage {
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::trading_venue::{
+        FeeBreakdown, QuoteResult,
+        error::TradingVenueError,
+        slippage::{max_amount_with_slippage, min_amount_with_slippage},
+    };
+
+    #[test]
+    fn test_max_amount_adds_slippage() {
+        assert_eq!(max_amount_with_slippage(10_000, 0).unwrap(), 10_000);
+        assert_eq!(max_amount_with_slippage(10_000, 50).unwrap(), 10_050);
+        assert_eq!(max_amount_with_slippage(10_000, 10_000).unwrap(), 20_000);
+    }
+
+    #[test]
+    fn test_max_amount_overflow_is_an_error() {
+        for (amount, slippage_bps) in [(u64::MAX, 1), (u64::MAX / 2 + 1, 10_000)] {
+            assert!(matches!(
+                max_amount_with_slippage(amount, slippage_bps),
+                Err(TradingVenueError::CheckedMathError(_))
+            ));
+        }
+
+        assert_eq!(max_amount_with_slippage(u64::MAX, 0).unwrap(), u64::MAX);
+        // Huge slippage on a small amount still fits.
+        assert!(max_amount_with_slippage(1, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_min_amount_never_overflows() {
+        assert_eq!(min_amount_with_slippage(u64::MAX, 0), u64::MAX);
+        assert_eq!(min_amount_with_slippage(u64::MAX, u64::MAX), 0);
+        assert_eq!(min_amount_with_slippage(10_000, 50), 9_950);
+    }
+
+    #[test]
+    fn test_minimum_received() {
+        let quote = QuoteResult {
+            input_mint: Pubkey::new_unique(),
+            output_mint: Pubkey::new_unique(),
+            amount: 1_000,
+            expected_output: 20_000,
+            fee_amount: 0,
+            fees: FeeBreakdown::default(),
+            price_impact_bps: 0,
+            not_enough_liquidity: false,
+        };
+
+        assert_eq!(quote.minimum_received(0), 20_000);
+        assert_eq!(quote.minimum_received(50), 19_900);
+        assert_eq!(quote.minimum_received(10_000), 0);
+        assert_eq!(quote.minimum_received(u64::MAX), 0);
+    }
+}
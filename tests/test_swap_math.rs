@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod swap_math {
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::oxedium::{
+        components::compute_swap_math,
+        states::{Treasury, Vault},
+        utils::OxediumVenueError,
+    };
+
+    /// $150 and $1 on the 1e-8 Pyth scale.
+    const SOL_PRICE: u64 = 15_000_000_000;
+    const USDC_PRICE: u64 = 100_000_000;
+
+    const ONE_SOL: u64 = 1_000_000_000;
+    const ONE_USDC: u64 = 1_000_000;
+
+    fn balanced_vault(liquidity: u64, base_fee: u64) -> Vault {
+        Vault {
+            is_active: true,
+            base_fee,
+            token_mint: Pubkey::new_unique(),
+            pyth_price_account: Pubkey::new_unique(),
+            initial_liquidity: liquidity,
+            current_liquidity: liquidity,
+            max_liquidity: u64::MAX,
+            ..Vault::default()
+        }
+    }
+
+    fn treasury(fee_bps: u64) -> Treasury {
+        Treasury {
+            fee_bps,
+            ..Treasury::default()
+        }
+    }
+
+    #[test]
+    fn test_fees_are_taken_from_raw_output() {
+        let sol = balanced_vault(1_000 * ONE_SOL, 30);
+        let usdc = balanced_vault(150_000 * ONE_USDC, 30);
+
+        let result = compute_swap_math(
+            ONE_SOL,
+            SOL_PRICE,
+            USDC_PRICE,
+            9,
+            6,
+            &sol,
+            &usdc,
+            &treasury(10),
+        )
+        .unwrap();
+
+        assert_eq!(result.raw_amount_out, 150 * ONE_USDC);
+        assert_eq!(result.swap_fee_bps, 30);
+        assert_eq!(result.lp_fee_amount, 450_000);
+        assert_eq!(result.protocol_fee_amount, 150_000);
+        assert_eq!(result.net_amount_out, 149_400_000);
+        assert_eq!(
+            result.net_amount_out + result.lp_fee_amount + result.protocol_fee_amount,
+            result.raw_amount_out
+        );
+    }
+
+    #[test]
+    fn test_decimals_are_rescaled_between_mints() {
+        let sol = balanced_vault(1_000 * ONE_SOL, 0);
+        let usdc = balanced_vault(150_000 * ONE_USDC, 0);
+
+        let to_sol = compute_swap_math(
+            150 * ONE_USDC,
+            USDC_PRICE,
+            SOL_PRICE,
+            6,
+            9,
+            &usdc,
+            &sol,
+            &treasury(0),
+        )
+        .unwrap();
+        assert_eq!(to_sol.net_amount_out, ONE_SOL);
+
+        // A 5-decimal token at $1 converts one-for-one in value.
+        let five_decimals = balanced_vault(10_000_000_000, 0);
+        let to_five = compute_swap_math(
+            ONE_USDC,
+            USDC_PRICE,
+            USDC_PRICE,
+            6,
+            5,
+            &usdc,
+            &five_decimals,
+            &treasury(0),
+        )
+        .unwrap();
+        assert_eq!(to_five.net_amount_out, 100_000);
+    }
+
+    #[test]
+    fn test_large_swaps_pay_ten_times_the_fee() {
+        let sol = balanced_vault(1_000 * ONE_SOL, 30);
+        // 150 USDC out is more than 10% of this 1_000 USDC vault.
+        let usdc = balanced_vault(1_000 * ONE_USDC, 30);
+
+        let result = compute_swap_math(
+            ONE_SOL,
+            SOL_PRICE,
+            USDC_PRICE,
+            9,
+            6,
+            &sol,
+            &usdc,
+            &treasury(0),
+        )
+        .unwrap();
+
+        assert_eq!(result.swap_fee_bps, 300);
+        assert_eq!(result.net_amount_out, 150 * ONE_USDC * 97 / 100);
+    }
+
+    #[test]
+    fn test_output_beyond_vault_liquidity_is_rejected() {
+        let sol = balanced_vault(1_000 * ONE_SOL, 30);
+        let usdc = balanced_vault(100 * ONE_USDC, 30);
+
+        let result = compute_swap_math(
+            ONE_SOL,
+            SOL_PRICE,
+            USDC_PRICE,
+            9,
+            6,
+            &sol,
+            &usdc,
+            &treasury(0),
+        );
+
+        assert!(matches!(result, Err(OxediumVenueError::NotEnoughLiquidity)));
+    }
+
+    #[test]
+    fn test_vault_without_initial_liquidity_is_a_math_error() {
+        let sol = balanced_vault(1_000 * ONE_SOL, 30);
+        let mut usdc = balanced_vault(150_000 * ONE_USDC, 30);
+        usdc.initial_liquidity = 0;
+
+        let result = compute_swap_math(
+            ONE_SOL,
+            SOL_PRICE,
+            USDC_PRICE,
+            9,
+            6,
+            &sol,
+            &usdc,
+            &treasury(0),
+        );
+
+        assert!(matches!(result, Err(OxediumVenueError::SwapMathError)));
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod token_info {
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::trading_venue::token_info::TokenInfo;
+
+    fn token(transfer_fee: Option<u16>, maximum_fee: Option<u64>) -> TokenInfo {
+        TokenInfo {
+            pubkey: Pubkey::new_unique(),
+            decimals: 6,
+            is_token_2022: transfer_fee.is_some(),
+            transfer_fee,
+            maximum_fee,
+        }
+    }
+
+    #[test]
+    fn test_amount_after_transfer_fee() {
+        let fee_free = token(None, None);
+        let fee_bearing = token(Some(25), Some(u64::MAX));
+
+        for amount in [0, 1, 399, 400, 1_000_000, u64::MAX] {
+            assert_eq!(fee_free.amount_after_transfer_fee(amount), amount);
+        }
+
+        // 0.25% rounded up, as on-chain.
+        assert_eq!(fee_bearing.amount_after_transfer_fee(1_000_000), 997_500);
+        assert_eq!(fee_bearing.amount_after_transfer_fee(401), 399);
+        assert_eq!(fee_bearing.amount_after_transfer_fee(1), 0);
+        assert_eq!(fee_bearing.amount_after_transfer_fee(0), 0);
+    }
+
+    #[test]
+    fn test_transfer_fee_is_capped_by_maximum_fee() {
+        let capped = token(Some(25), Some(1_000));
+
+        assert_eq!(capped.transfer_fee_for(100_000), 250);
+        assert_eq!(capped.transfer_fee_for(400_000), 1_000);
+        assert_eq!(capped.transfer_fee_for(u64::MAX), 1_000);
+        assert_eq!(capped.amount_after_transfer_fee(10_000_000), 9_999_000);
+    }
+
+    /// `gross` is the smallest amount that nets at least `net`.
+    fn assert_minimal_gross(token: &TokenInfo, net: u64) {
+        let gross = token.amount_before_transfer_fee(net);
+        assert!(token.amount_after_transfer_fee(gross) >= net);
+        if gross > 0 {
+            assert!(token.amount_after_transfer_fee(gross - 1) < net);
+        }
+    }
+
+    #[test]
+    fn test_amount_before_transfer_fee_uncapped() {
+        let token = token(Some(25), None);
+
+        assert_eq!(token.amount_before_transfer_fee(0), 0);
+        assert_eq!(token.amount_before_transfer_fee(997_500), 1_000_000);
+        assert_eq!(token.amount_before_transfer_fee(399), 400);
+        for net in [1, 2, 398, 399, 400, 997_499, 997_500, 997_501, 123_456_789] {
+            assert_minimal_gross(&token, net);
+        }
+        assert_eq!(token.amount_before_transfer_fee(u64::MAX), u64::MAX);
+        assert_eq!(token(None, None).amount_before_transfer_fee(42), 42);
+    }
+
+    #[test]
+    fn test_amount_before_transfer_fee_capped() {
+        let token = token(Some(25), Some(1_000));
+
+        assert_eq!(token.amount_before_transfer_fee(10_000_000), 10_001_000);
+        assert_eq!(token.amount_before_transfer_fee(u64::MAX - 1_000), u64::MAX);
+        for net in [10_000_000, 50_000_000, u64::MAX / 2] {
+            assert_minimal_gross(&token, net);
+        }
+    }
+
+    #[test]
+    fn test_amount_before_transfer_fee_at_cap_boundary() {
+        // The fee first reaches the 1_000 cap at a gross of 400_000, which
+        // nets 399_000. Around that point the two regimes meet.
+        let token = token(Some(25), Some(1_000));
+
+        assert_eq!(token.transfer_fee_for(399_999), 1_000);
+        assert_eq!(token.transfer_fee_for(399_600), 999);
+        assert_eq!(token.amount_before_transfer_fee(399_000), 400_000);
+        for net in 398_590..=399_010 {
+            assert_minimal_gross(&token, net);
+        }
+    }
+}
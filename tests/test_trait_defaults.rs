@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod trait_defaults {
+    use std::collections::HashMap;
+
+    use async_trait::async_trait;
+    use solana_account::Account;
+    use solana_instruction::Instruction;
+    use solana_pubkey::Pubkey;
+
+    use titan_integration_template::account_caching::AccountsCache;
+    use titan_integration_template::trading_venue::{
+        QuoteRequest, QuoteResult, TradingVenue, error::TradingVenueError, protocol::PoolProtocol,
+        token_info::TokenInfo,
+    };
+
+    /// Venue implementing only the required `TradingVenue` methods. Its state
+    /// is the lamport balance of a single account.
+    struct MinimalVenue {
+        key: Pubkey,
+        lamports: Option<u64>,
+        tokens: Vec<TokenInfo>,
+    }
+
+    impl MinimalVenue {
+        fn new() -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                lamports: None,
+                tokens: vec![],
+            }
+        }
+    }
+
+    #[async_trait]
+    impl TradingVenue for MinimalVenue {
+        fn initialized(&self) -> bool {
+            self.lamports.is_some()
+        }
+
+        fn program_id(&self) -> Pubkey {
+            Pubkey::default()
+        }
+
+        fn program_dependencies(&self) -> Vec<Pubkey> {
+            vec![]
+        }
+
+        fn market_id(&self) -> Pubkey {
+            self.key
+        }
+
+        fn get_token_info(&self) -> &[TokenInfo] {
+            &self.tokens
+        }
+
+        fn protocol(&self) -> PoolProtocol {
+            PoolProtocol::Oxedium
+        }
+
+        fn get_required_pubkeys_for_update(&self) -> Result<Vec<Pubkey>, TradingVenueError> {
+            Ok(vec![self.key])
+        }
+
+        async fn update_state(
+            &mut self,
+            cache: &dyn AccountsCache,
+        ) -> Result<(), TradingVenueError> {
+            let account = cache
+                .get_account(&self.key)
+                .await?
+                .ok_or(TradingVenueError::NoAccountFound(self.key.into()))?;
+            self.lamports = Some(account.lamports);
+            Ok(())
+        }
+
+        fn quote(&self, _request: QuoteRequest) -> Result<QuoteResult, TradingVenueError> {
+            unreachable!("not exercised by these tests")
+        }
+
+        fn generate_swap_instruction(
+            &self,
+            _request: QuoteRequest,
+            _user: Pubkey,
+        ) -> Result<Instruction, TradingVenueError> {
+            unreachable!("not exercised by these tests")
+        }
+    }
+
+    #[test]
+    fn test_update_from_accounts_defaults_to_update_state() {
+        let mut venue = MinimalVenue::new();
+        let accounts = HashMap::from([(
+            venue.key,
+            Account {
+                lamports: 42,
+                ..Account::default()
+            },
+        )]);
+
+        venue.update_state_from_accounts(&accounts).unwrap();
+        assert!(venue.initialized());
+        assert_eq!(venue.lamports, Some(42));
+
+        let mut empty = MinimalVenue::new();
+        assert_eq!(
+            empty
+                .update_state_from_accounts(&HashMap::new())
+                .unwrap_err(),
+            TradingVenueError::NoAccountFound(empty.key.into())
+        );
+        assert!(!empty.initialized());
+    }
+
+    #[test]
+    fn test_fee_bps_defaults_to_unsupported() {
+        assert!(matches!(
+            MinimalVenue::new().fee_bps(),
+            Err(TradingVenueError::UnsupportedVenue(_))
+        ));
+    }
+
+    #[test]
+    fn test_swap_accounts_default_to_unsupported() {
+        let venue = MinimalVenue::new();
+        let (mint_a, mint_b, user) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        assert!(matches!(
+            venue.get_swap_accounts(mint_a, mint_b, user),
+            Err(TradingVenueError::UnsupportedVenue(_))
+        ));
+    }
+
+    #[test]
+    fn test_token_index_beyond_u8_is_rejected() {
+        let mut venue = MinimalVenue::new();
+        venue.tokens = (0..300)
+            .map(|_| TokenInfo {
+                pubkey: Pubkey::new_unique(),
+                ..TokenInfo::default()
+            })
+            .collect();
+        let (first, last) = (venue.tokens[0].pubkey, venue.tokens[299].pubkey);
+
+        // Truncated to `u8`, index 299 would silently alias token 43.
+        assert_eq!(
+            venue.price_ratio(&last, &first).unwrap_err(),
+            TradingVenueError::TokenInfoIndexError(299)
+        );
+    }
+}
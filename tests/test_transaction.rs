@@ -0,0 +1,202 @@
+mod common;
+
+#[cfg(test)]
+mod transaction {
+    use solana_compute_budget_interface::ComputeBudgetInstruction;
+    use solana_hash::Hash;
+    use solana_instruction::Instruction;
+    use solana_pubkey::Pubkey;
+    use solana_sdk::message::{AddressLookupTableAccount, VersionedMessage};
+    use spl_associated_token_account::get_associated_token_address;
+
+    use titan_integration_template::oxedium::amm::{OXEDIUM_AMM_PROGRAM_ID, OxediumAmmVenue};
+    use titan_integration_template::trading_venue::{
+        QuoteRequest, SwapType, TradingVenue,
+        tx::{build_swap_instructions, build_swap_message, build_swap_transaction},
+    };
+
+    use crate::common::{OxediumFixture, SOL_MINT, USDC_MINT, USDC_ORACLE, price_update};
+
+    fn sol_to_usdc() -> QuoteRequest {
+        QuoteRequest::new(SOL_MINT, USDC_MINT, 1_000_000_000, SwapType::ExactIn)
+    }
+
+    #[tokio::test]
+    async fn test_compute_unit_limit_is_prepended() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+
+        let tx = build_swap_transaction(
+            &venue,
+            sol_to_usdc(),
+            user,
+            Hash::new_unique(),
+            Some(300_000),
+        )
+        .unwrap();
+        let message = &tx.message;
+        let keys = message.static_account_keys();
+        let instructions = message.instructions();
+
+        assert_eq!(keys[0], user);
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(instructions.len(), 3);
+
+        let budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(300_000);
+        assert_eq!(
+            *instructions[0].program_id(keys),
+            solana_sdk_ids::compute_budget::ID
+        );
+        assert_eq!(instructions[0].data, budget_ix.data);
+        assert_eq!(
+            *instructions[1].program_id(keys),
+            spl_associated_token_account::ID
+        );
+        assert_eq!(*instructions[2].program_id(keys), OXEDIUM_AMM_PROGRAM_ID);
+    }
+
+    #[tokio::test]
+    async fn test_no_compute_unit_limit_builds_venue_instructions_only() {
+        let venue = OxediumFixture::new().venue().await;
+
+        let tx = build_swap_transaction(
+            &venue,
+            sol_to_usdc(),
+            Pubkey::new_unique(),
+            Hash::new_unique(),
+            None,
+        )
+        .unwrap();
+        let keys = tx.message.static_account_keys();
+        let instructions = tx.message.instructions();
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            *instructions[0].program_id(keys),
+            spl_associated_token_account::ID
+        );
+        assert_eq!(*instructions[1].program_id(keys), OXEDIUM_AMM_PROGRAM_ID);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_tables_shrink_static_keys() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: venue
+                .get_swap_accounts(SOL_MINT, USDC_MINT, user)
+                .unwrap()
+                .into_iter()
+                .map(|meta| meta.pubkey)
+                .filter(|key| *key != user)
+                .collect(),
+        };
+
+        let plain = build_swap_message(&venue, sol_to_usdc(), user, blockhash, None, &[]).unwrap();
+        let compressed = build_swap_message(
+            &venue,
+            sol_to_usdc(),
+            user,
+            blockhash,
+            None,
+            std::slice::from_ref(&table),
+        )
+        .unwrap();
+
+        let VersionedMessage::V0(compressed) = compressed else {
+            panic!("expected a v0 message");
+        };
+        assert_eq!(compressed.address_table_lookups.len(), 1);
+        assert_eq!(compressed.address_table_lookups[0].account_key, table.key);
+        assert_eq!(compressed.account_keys.len(), 3);
+        assert_eq!(compressed.account_keys[0], user);
+        assert!(
+            compressed
+                .account_keys
+                .contains(&spl_associated_token_account::ID)
+        );
+        assert!(compressed.account_keys.contains(&OXEDIUM_AMM_PROGRAM_ID));
+        assert!(compressed.account_keys.len() < plain.static_account_keys().len());
+
+        // The user's output ATA is still created ahead of the swap.
+        let programs: Vec<Pubkey> = compressed
+            .instructions
+            .iter()
+            .map(|ix| compressed.account_keys[ix.program_id_index as usize])
+            .collect();
+        assert_eq!(
+            programs,
+            vec![spl_associated_token_account::ID, OXEDIUM_AMM_PROGRAM_ID]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sol_is_wrapped_and_unwrapped() {
+        let venue = OxediumFixture::new().venue().await;
+        let user = Pubkey::new_unique();
+        let programs =
+            |ixs: &[Instruction]| -> Vec<Pubkey> { ixs.iter().map(|ix| ix.program_id).collect() };
+
+        let sol_in = build_swap_instructions(&venue, sol_to_usdc(), user, true).unwrap();
+        assert_eq!(
+            programs(&sol_in),
+            vec![
+                spl_associated_token_account::ID,
+                solana_sdk_ids::system_program::ID,
+                spl_token::ID,
+                spl_associated_token_account::ID,
+                OXEDIUM_AMM_PROGRAM_ID,
+                spl_token::ID,
+            ]
+        );
+
+        let usdc_to_sol = QuoteRequest::new(USDC_MINT, SOL_MINT, 1_000_000, SwapType::ExactIn);
+        let sol_out = build_swap_instructions(&venue, usdc_to_sol.clone(), user, true).unwrap();
+        assert_eq!(
+            programs(&sol_out),
+            vec![
+                spl_associated_token_account::ID,
+                OXEDIUM_AMM_PROGRAM_ID,
+                spl_token::ID,
+            ]
+        );
+
+        let wsol_ata = get_associated_token_address(&user, &spl_token::native_mint::ID);
+        for ixs in [&sol_in, &sol_out] {
+            assert!(ixs[0].accounts.iter().any(|meta| meta.pubkey == wsol_ata));
+            assert_eq!(ixs.last().unwrap().accounts[0].pubkey, wsol_ata);
+        }
+
+        let unwrapped = build_swap_instructions(&venue, usdc_to_sol, user, false).unwrap();
+        assert_eq!(
+            programs(&unwrapped),
+            vec![spl_associated_token_account::ID, OXEDIUM_AMM_PROGRAM_ID]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_wrapping_without_native_mint() {
+        let mut fixture = OxediumFixture::new();
+        let (other_mint, other_oracle) = (Pubkey::new_unique(), Pubkey::new_unique());
+        fixture.add_token(other_mint, other_oracle, 6, 150_000_000_000, 30);
+        fixture
+            .oracles
+            .insert(other_oracle, price_update(100_000_000, -8));
+
+        let mut venue = OxediumAmmVenue::new(
+            fixture.market,
+            vec![(USDC_MINT, USDC_ORACLE), (other_mint, other_oracle)],
+        );
+        venue.update_state(&fixture.cache()).await.unwrap();
+
+        let request = QuoteRequest::new(USDC_MINT, other_mint, 1_000_000, SwapType::ExactIn);
+        let instructions =
+            build_swap_instructions(&venue, request, Pubkey::new_unique(), true).unwrap();
+
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[1].program_id, OXEDIUM_AMM_PROGRAM_ID);
+    }
+}